@@ -0,0 +1,162 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Checks whether a field may be left out of a builder, which is the case for
+/// `Option` fields and fields marked with `#[enum_methods(default)]`.
+fn is_optional_field(field: &Field) -> bool {
+    let is_option = match field.ty {
+        Ty::Path(None, ref path) => path.segments.last().map(|s| s.ident == "Option").unwrap_or(false),
+        _ => false,
+    };
+    is_option || has_enum_methods_word(&field.attrs, "default")
+}
+
+/// Gives builders for struct variants marked with `#[enum_methods(builder)]`.
+pub(crate) fn impl_enum_builders(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let builders = variants.iter()
+        .filter(|v| has_enum_methods_word(&v.attrs, "builder"))
+        .map(|v| {
+            let variant_name = &v.ident;
+            let fields = match v.data {
+                VariantData::Struct(ref fields) => fields,
+                _ => panic!("#[enum_methods(builder)] may only be used on struct variants, but {}::{} is not one",
+                            name, variant_name),
+            };
+
+            let builder: Ident = format!("{}{}Builder", name, variant_name).into();
+            let builder_fn: Ident = format!("{}_builder", to_snake_case(&v.ident)).into();
+
+            // every required field has a type parameter, which is `PhantomData<T>` until the field is
+            // set and `T` afterwards, so `build()` only exists once all of them are set
+            let required = fields.iter().map(|f| !is_optional_field(f)).collect::<Vec<_>>();
+            let params = fields.iter()
+                .zip(required.iter())
+                .filter(|&(_, &required)| required)
+                .enumerate()
+                .map(|(i, _)| Ident::from(format!("__F{}", i)))
+                .collect::<Vec<_>>();
+            let required_types = fields.iter()
+                .zip(required.iter())
+                .filter(|&(_, &required)| required)
+                .map(|(f, _)| f.ty.clone())
+                .collect::<Vec<_>>();
+            let unset_types = required_types.iter()
+                .map(|ty| quote! { ::std::marker::PhantomData<#ty> })
+                .collect::<Vec<_>>();
+
+            let mut param_index = 0;
+            let storage_types = fields.iter()
+                .zip(required.iter())
+                .map(|(f, &required)| {
+                    let ty = &f.ty;
+                    if required {
+                        let param = &params[param_index];
+                        param_index += 1;
+                        quote! { #param }
+                    }
+                    else {
+                        quote! { Option<#ty> }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+            let initial_values = required.iter()
+                .map(|&required| if required { quote! { ::std::marker::PhantomData } } else { quote! { None } })
+                .collect::<Vec<_>>();
+
+            let mut param_index = 0;
+            let setters = fields.iter()
+                .zip(required.iter())
+                .map(|(f, &required)| {
+                    let field_name = f.ident.as_ref().unwrap();
+                    let ty = &f.ty;
+                    if !required {
+                        return quote! {
+                            pub fn #field_name(mut self, #field_name: impl Into<#ty>) -> Self {
+                                self.#field_name = Some(#field_name.into());
+                                self
+                            }
+                        };
+                    }
+                    // setting a required field changes its type parameter, so the builder is rebuilt
+                    let result_params = params.iter()
+                        .enumerate()
+                        .map(|(i, param)| if i == param_index { quote! { #ty } } else { quote! { #param } })
+                        .collect::<Vec<_>>();
+                    param_index += 1;
+                    let values = field_names.iter()
+                        .map(|other| if other == field_name { quote! { #field_name.into() } } else { quote! { self.#other } })
+                        .collect::<Vec<_>>();
+                    let field_names = field_names.clone();
+                    quote! {
+                        pub fn #field_name(self, #field_name: impl Into<#ty>) -> #builder<#(#result_params),*> {
+                            #builder {
+                                #(#field_names: #values),*
+                            }
+                        }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let field_values = fields.iter()
+                .map(|f| {
+                    let field_name = f.ident.as_ref().unwrap();
+                    if is_optional_field(f) {
+                        quote! { self.#field_name.unwrap_or_default() }
+                    }
+                    else {
+                        quote! { self.#field_name }
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let field_names2 = field_names.clone();
+            let field_names3 = field_names.clone();
+            let params2 = params.clone();
+            let params3 = params.clone();
+
+            quote! {
+                #vis struct #builder<#(#params),*> {
+                    #(#field_names: #storage_types),*
+                }
+
+                #[allow(dead_code)]
+                impl #name {
+                    pub fn #builder_fn() -> #builder<#(#unset_types),*> {
+                        #builder {
+                            #(#field_names2: #initial_values),*
+                        }
+                    }
+                }
+
+                #[allow(dead_code)]
+                impl<#(#params2),*> #builder<#(#params3),*> {
+                    #(#setters)*
+                }
+
+                #[allow(dead_code)]
+                impl #builder<#(#required_types),*> {
+                    /// Builds the variant, which is only possible once every required field is set.
+                    pub fn build(self) -> #name {
+                        #name::#variant_name {
+                            #(#field_names3: #field_values),*
+                        }
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#builders)*
+    }
+}
@@ -0,0 +1,72 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `same_variant` and `by_variant` methods along with `{Enum}ByVariant`, which compares and
+/// hashes references to the enum by their variant alone.
+pub(crate) fn impl_enum_by_variant(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let by_variant: Ident = format!("{}ByVariant", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let patterns = variants.iter()
+        .map(|v| variant_wildcard_pattern(name, v))
+        .collect::<Vec<_>>();
+    let ordinals = (0..variants.len()).collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            /// Checks whether both values are the same variant, ignoring their data.
+            pub fn same_variant(&self, other: &Self) -> bool {
+                ::std::mem::discriminant(self) == ::std::mem::discriminant(other)
+            }
+
+            /// Wraps a reference to this value so it is compared and hashed by its variant alone.
+            pub fn by_variant(&self) -> #by_variant {
+                #by_variant(self)
+            }
+        }
+
+        #[derive(Clone, Copy)]
+        #vis struct #by_variant<'a>(pub &'a #name);
+
+        impl<'a> #by_variant<'a> {
+            fn ordinal(&self) -> usize {
+                match *self.0 {
+                    #(#patterns => #ordinals),*
+                }
+            }
+        }
+
+        impl<'a> PartialEq for #by_variant<'a> {
+            fn eq(&self, other: &Self) -> bool {
+                self.ordinal() == other.ordinal()
+            }
+        }
+
+        impl<'a> Eq for #by_variant<'a> {}
+
+        impl<'a> PartialOrd for #by_variant<'a> {
+            fn partial_cmp(&self, other: &Self) -> Option<::std::cmp::Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<'a> Ord for #by_variant<'a> {
+            fn cmp(&self, other: &Self) -> ::std::cmp::Ordering {
+                self.ordinal().cmp(&other.ordinal())
+            }
+        }
+
+        impl<'a> ::std::hash::Hash for #by_variant<'a> {
+            fn hash<H: ::std::hash::Hasher>(&self, state: &mut H) {
+                self.ordinal().hash(state)
+            }
+        }
+    }
+}
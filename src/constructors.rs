@@ -0,0 +1,66 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `new_*` constructors for tuple and struct variants, taking anything that converts into
+/// the type of each member.
+pub(crate) fn impl_enum_constructors(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let constructors = variants.iter()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let function_name: Ident = format!("new_{}", to_snake_case(&v.ident)).into();
+            let types = v.data.fields().iter().map(|f| &f.ty).collect::<Vec<_>>();
+
+            match v.data {
+                VariantData::Tuple(ref fields) => {
+                    let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+                    let args2 = args.clone();
+                    let mut tokens = quote! {
+                        pub fn #function_name(#(#args: impl Into<#types>),*) -> Self {
+                            #name::#variant_name(#(#args2.into()),*)
+                        }
+                    };
+
+                    // taking the members as one tuple lets an existing tuple be passed along as-is
+                    if fields.len() > 1 {
+                        let from_name: Ident = format!("new_{}_from", to_snake_case(&v.ident)).into();
+                        let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                        let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+                        let args2 = args.clone();
+                        tokens.append(quote! {
+                            pub fn #from_name((#(#args),*): (#(#types),*)) -> Self {
+                                #name::#variant_name(#(#args2),*)
+                            }
+                        });
+                    }
+
+                    tokens
+                }
+                VariantData::Struct(ref fields) => {
+                    let field_names = fields.iter().map(|f| f.ident.as_ref().unwrap()).collect::<Vec<_>>();
+                    let field_names2 = field_names.clone();
+                    let field_names3 = field_names.clone();
+                    quote! {
+                        pub fn #function_name(#(#field_names: impl Into<#types>),*) -> Self {
+                            #name::#variant_name { #(#field_names2: #field_names3.into()),* }
+                        }
+                    }
+                }
+                VariantData::Unit => quote! {},
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#constructors)*
+        }
+    }
+}
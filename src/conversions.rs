@@ -0,0 +1,86 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `From<T> for Enum` and `TryFrom<Enum> for T` for every single-member tuple variant whose
+/// type is either unique among those variants, or which is marked with `#[enum_methods(from)]`.
+pub(crate) fn impl_enum_conversions(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! conversion_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() == 1 } else { false })
+        };
+    }
+
+    // types are compared by their tokens, so e.g. `String` and `std::string::String` are distinct
+    let type_strs = conversion_filter!()
+        .map(|v| {
+            let ty = &v.data.fields()[0].ty;
+            quote! { #ty }.to_string()
+        })
+        .collect::<Vec<String>>();
+
+    for ty in &type_strs {
+        let marked = conversion_filter!()
+            .zip(type_strs.iter())
+            .filter(|&(v, t)| t == ty && has_enum_methods_word(&v.attrs, "from"))
+            .map(|(v, _)| v.ident.to_string())
+            .collect::<Vec<_>>();
+        if marked.len() > 1 {
+            panic!("only one variant of {} holding {} may be marked with #[enum_methods(from)], but {} are",
+                   name, ty, marked.join(", "));
+        }
+    }
+
+    let converted = conversion_filter!()
+        .zip(type_strs.iter())
+        .filter(|&(v, ty)| {
+            has_enum_methods_word(&v.attrs, "from") || type_strs.iter().filter(|t| *t == ty).count() == 1
+        })
+        .map(|(v, _)| v)
+        .collect::<Vec<_>>();
+
+    let variant_names = converted.iter()
+        .map(|v| v.ident.clone())
+        .collect::<Vec<Ident>>();
+
+    let variant_types = converted.iter()
+        .map(|v| v.data.fields()[0].ty.clone())
+        .collect::<Vec<Ty>>();
+
+    let names = vec!(name.clone(); variant_names.len());
+    let names2 = names.clone();
+    let names3 = names.clone();
+    let names4 = names.clone();
+    let names5 = names.clone();
+    let names6 = names.clone();
+    let variant_names2 = variant_names.clone();
+    let variant_types2 = variant_types.clone();
+    let variant_types3 = variant_types.clone();
+
+    quote! {
+        #(impl From<#variant_types> for #names {
+            fn from(v: #variant_types2) -> Self {
+                #names2::#variant_names(v)
+            }
+        })*
+
+        #(#[allow(unreachable_patterns)]
+        impl ::std::convert::TryFrom<#names3> for #variant_types3 {
+            type Error = #names4;
+
+            fn try_from(value: #names5) -> Result<Self, Self::Error> {
+                match value {
+                    #names6::#variant_names2(v) => Ok(v),
+                    value => Err(value),
+                }
+            }
+        })*
+    }
+}
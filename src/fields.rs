@@ -0,0 +1,171 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `Option`-returning accessors and setters for every named field of every struct variant.
+pub(crate) fn impl_enum_field_accessors(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! field_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Struct(_) = v.data { true } else { false })
+        };
+    }
+
+    let accessors = field_filter!()
+        .flat_map(|v| v.data.fields().iter().map(move |f| (v, f)))
+        .map(|(v, f)| {
+            let variant_name = &v.ident;
+            let field_name = f.ident.as_ref().unwrap();
+            let ty = &f.ty;
+            let prefix = format!("{}_{}", to_snake_case(&v.ident), field_name);
+            let getter_name: Ident = prefix.clone().into();
+            let getter_mut_name: Ident = format!("{}_mut", prefix).into();
+            let setter_name: Ident = format!("set_{}", prefix).into();
+
+            quote! {
+                pub fn #getter_name(&self) -> Option<&#ty> {
+                    match *self {
+                        #name::#variant_name { ref #field_name, .. } => Some(#field_name),
+                        _ => None,
+                    }
+                }
+
+                pub fn #getter_mut_name(&mut self) -> Option<&mut #ty> {
+                    match *self {
+                        #name::#variant_name { ref mut #field_name, .. } => Some(#field_name),
+                        _ => None,
+                    }
+                }
+
+                /// Sets the field, giving the value back if the enum is a different variant.
+                pub fn #setter_name(&mut self, value: #ty) -> Result<(), #ty> {
+                    match *self {
+                        // the field is bound under another name, since it may itself be named `value`
+                        #name::#variant_name { #field_name: ref mut field, .. } => {
+                            *field = value;
+                            Ok(())
+                        }
+                        _ => Err(value),
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code, unreachable_patterns)]
+        impl #name {
+            #(#accessors)*
+        }
+    }
+}
+
+/// Gives accessors for fields which are shared by several struct variants. Fields found in every
+/// variant get total accessors, while the others return an `Option`.
+pub(crate) fn impl_enum_shared_fields(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let selected = enum_methods_list(&ast.attrs, "shared")
+        .map(|items| meta_words(items, "shared"));
+
+    // fields are kept in the order they first appear, along with every variant holding them
+    let mut fields: Vec<(&Ident, &Ty, Vec<&Variant>)> = Vec::new();
+    for variant in variants {
+        if let VariantData::Struct(ref variant_fields) = variant.data {
+            for field in variant_fields {
+                let field_name = field.ident.as_ref().unwrap();
+                match fields.iter().position(|&(f, _, _)| f == field_name) {
+                    Some(i) => fields[i].2.push(variant),
+                    None => fields.push((field_name, &field.ty, vec![variant])),
+                }
+            }
+        }
+    }
+
+    if let Some(ref selected) = selected {
+        if let Some(unknown) = selected.iter().find(|s| !fields.iter().any(|&(f, _, _)| f == *s)) {
+            panic!("shared field `{}` is not a field of any variant of {}", unknown, name);
+        }
+    }
+
+    let accessors = fields.iter()
+        .filter(|&&(field_name, _, ref holders)| match selected {
+            Some(ref selected) => selected.contains(field_name),
+            None => holders.len() > 1,
+        })
+        .filter(|&&(field_name, ty, ref holders)| {
+            // a field which has a different type in some variant can't have one accessor
+            let ty_str = quote! { #ty }.to_string();
+            let same_type = holders.iter()
+                .flat_map(|v| v.data.fields().iter())
+                .filter(|f| f.ident.as_ref() == Some(field_name))
+                .all(|f| {
+                    let fty = &f.ty;
+                    quote! { #fty }.to_string() == ty_str
+                });
+            if !same_type && selected.is_some() {
+                panic!("shared field `{}` does not have the same type in every variant of {}", field_name, name);
+            }
+            same_type
+        })
+        .map(|&(field_name, ty, ref holders)| {
+            let getter_mut_name: Ident = format!("{}_mut", field_name).into();
+            let names = vec!(name.clone(); holders.len());
+            let holder_names = holders.iter().map(|v| v.ident.clone()).collect::<Vec<_>>();
+            let field_names = vec!(field_name.clone(); holders.len());
+            let names2 = names.clone();
+            let holder_names2 = holder_names.clone();
+            let field_names2 = field_names.clone();
+
+            if holders.len() == variants.len() {
+                quote! {
+                    pub fn #field_name(&self) -> &#ty {
+                        match *self {
+                            #(#names::#holder_names { ref #field_names, .. })|* => #field_name,
+                        }
+                    }
+
+                    pub fn #getter_mut_name(&mut self) -> &mut #ty {
+                        match *self {
+                            #(#names2::#holder_names2 { ref mut #field_names2, .. })|* => #field_name,
+                        }
+                    }
+                }
+            }
+            else {
+                quote! {
+                    pub fn #field_name(&self) -> Option<&#ty> {
+                        match *self {
+                            #(#names::#holder_names { ref #field_names, .. })|* => Some(#field_name),
+                            _ => None,
+                        }
+                    }
+
+                    pub fn #getter_mut_name(&mut self) -> Option<&mut #ty> {
+                        match *self {
+                            #(#names2::#holder_names2 { ref mut #field_names2, .. })|* => Some(#field_name),
+                            _ => None,
+                        }
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#accessors)*
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives a `fold` method taking one closure per variant, in declaration order.
+pub(crate) fn impl_enum_fold(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    // the prefix keeps variants like `Fn` or `Type` from naming a closure after a keyword
+    let closure_names = variants.iter()
+        .map(|v| format!("on_{}", to_snake_case(&v.ident)).into())
+        .collect::<Vec<Ident>>();
+
+    // the type parameters are prefixed so they can't hide the enum or the types it holds
+    let closure_types = (0..variants.len())
+        .map(|i| format!("__F{}", i).into())
+        .collect::<Vec<Ident>>();
+
+    // like the getters, a single member is passed as-is and several members as a tuple
+    let closure_args = variants.iter()
+        .map(|v| {
+            let types = v.data.fields().iter().map(|f| &f.ty).collect::<Vec<_>>();
+            match types.len() {
+                0 => quote! {},
+                1 => quote! { #(#types)* },
+                _ => quote! { (#(#types),*) },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let arms = variants.iter()
+        .zip(closure_names.iter())
+        .map(|(v, closure_name)| {
+            let variant_name = &v.ident;
+            let fields = v.data.fields();
+            // struct fields are bound to generated names too, which can't shadow the `on_*` closures
+            let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+            let args2 = args.clone();
+            let pattern = match v.data {
+                VariantData::Tuple(_) => quote! { #name::#variant_name(#(#args),*) },
+                VariantData::Struct(_) => {
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    quote! { #name::#variant_name { #(#field_names: #args),* } }
+                }
+                VariantData::Unit => quote! { #name::#variant_name },
+            };
+            let value = match fields.len() {
+                0 | 1 => quote! { #(#args2)* },
+                _ => quote! { (#(#args2),*) },
+            };
+            quote! { #pattern => #closure_name(#value) }
+        })
+        .collect::<Vec<_>>();
+
+    let closure_types2 = closure_types.clone();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            /// Consumes the enum, passing its data to the closure matching its variant.
+            #[allow(clippy::too_many_arguments)]
+            pub fn fold<__R, #(#closure_types: FnOnce(#closure_args) -> __R),*>(self, #(#closure_names: #closure_types2),*) -> __R {
+                match self {
+                    #(#arms),*
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+use syn::*;
+use quote;
+
+/// Checks whether a type mentions the given type parameter anywhere.
+fn mentions_ty_param(ty: &Ty, param: &Ident) -> bool {
+    let tokens = quote! { #ty };
+    tokens.as_str()
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .any(|word| word == param.as_ref())
+}
+
+/// Checks whether a type is exactly the given type parameter.
+fn is_ty_param(ty: &Ty, param: &Ident) -> bool {
+    match *ty {
+        Ty::Path(None, ref path) => {
+            !path.global && path.segments.len() == 1 && path.segments[0].ident == *param
+                && path.segments[0].parameters.is_empty()
+        }
+        _ => false,
+    }
+}
+
+/// Gets the name and type arguments of a type written as a single path segment, e.g. `Vec` and
+/// `[T]` for `Vec<T>`.
+fn single_segment(ty: &Ty) -> Option<(&Ident, &[Ty])> {
+    match *ty {
+        Ty::Path(None, ref path) if path.segments.len() == 1 => {
+            let segment = &path.segments[0];
+            match segment.parameters {
+                PathParameters::AngleBracketed(ref data) => Some((&segment.ident, &data.types[..])),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// The method a member is rewritten for.
+#[derive(Clone, Copy, PartialEq)]
+enum Rewrite {
+    Map,
+    AsRef,
+    AsMut,
+}
+
+/// Rewrites a member which mentions the type parameter, given an expression for it that is owned
+/// for `map` and borrowed for `as_ref` and `as_mut`. The parameter itself, `Option`, `Box` and
+/// `Vec` of supported types, and the enum itself with the same parameter are supported.
+fn rewrite_member(ty: &Ty, param: &Ident, name: &Ident, expr: quote::Tokens, rewrite: Rewrite, depth: usize)
+    -> Option<quote::Tokens>
+{
+    if is_ty_param(ty, param) {
+        return Some(match rewrite {
+            Rewrite::Map => quote! { f(#expr) },
+            _ => expr,
+        });
+    }
+
+    let (ident, types) = single_segment(ty)?;
+    let inner = types.first()?;
+    if types.len() != 1 {
+        return None;
+    }
+    let x: Ident = format!("x{}", depth).into();
+
+    if ident == name {
+        if !is_ty_param(inner, param) {
+            return None;
+        }
+        // the recursive call reuses the same closure, so there is only one instantiation of `map`
+        return Some(match rewrite {
+            Rewrite::Map => quote! { #expr.map_with(f) },
+            Rewrite::AsRef => quote! { #name::as_ref(#expr) },
+            Rewrite::AsMut => quote! { #name::as_mut(#expr) },
+        });
+    }
+
+    if ident == "Option" {
+        let value = rewrite_member(inner, param, name, quote! { #x }, rewrite, depth + 1)?;
+        Some(match rewrite {
+            Rewrite::Map => quote! { #expr.map(|#x| #value) },
+            Rewrite::AsRef => quote! { #expr.as_ref().map(|#x| #value) },
+            Rewrite::AsMut => quote! { #expr.as_mut().map(|#x| #value) },
+        })
+    }
+    else if ident == "Box" {
+        let inner_expr = match rewrite {
+            Rewrite::Map => quote! { (*#expr) },
+            Rewrite::AsRef => quote! { (&**#expr) },
+            Rewrite::AsMut => quote! { (&mut **#expr) },
+        };
+        let value = rewrite_member(inner, param, name, inner_expr, rewrite, depth + 1)?;
+        Some(quote! { Box::new(#value) })
+    }
+    else if ident == "Vec" {
+        let value = rewrite_member(inner, param, name, quote! { #x }, rewrite, depth + 1)?;
+        Some(match rewrite {
+            Rewrite::Map => quote! { #expr.into_iter().map(|#x| #value).collect::<Vec<_>>() },
+            Rewrite::AsRef => quote! { #expr.iter().map(|#x| #value).collect::<Vec<_>>() },
+            Rewrite::AsMut => quote! { #expr.iter_mut().map(|#x| #value).collect::<Vec<_>>() },
+        })
+    }
+    else {
+        None
+    }
+}
+
+/// Gives `map`, `as_ref` and `as_mut` for enums with a single type parameter, which transform every
+/// occurrence of that parameter in every member.
+pub(crate) fn impl_enum_functor(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let generics = &ast.generics;
+    if generics.ty_params.len() != 1 {
+        panic!("EnumFunctor requires {} to have exactly one type parameter", name);
+    }
+    let param = &generics.ty_params[0].ident;
+    // the bounds on the parameter would also have to hold for whatever it is mapped to
+    if !generics.ty_params[0].bounds.is_empty() || !generics.where_clause.predicates.is_empty() {
+        panic!("EnumFunctor does not support bounds on the type parameter of {}", name);
+    }
+
+    let mapped: Ident = "__U".into();
+    let lifetimes = generics.lifetimes.iter().map(|l| &l.lifetime).collect::<Vec<_>>();
+    let lifetimes2 = lifetimes.clone();
+    let lifetimes3 = lifetimes.clone();
+    let lifetimes4 = lifetimes.clone();
+    let (impl_generics, ty_generics, _) = generics.split_for_impl();
+
+    // members which aren't the type parameter are moved by `map`, but have to be cloned by
+    // `as_ref` and `as_mut`
+    let mut cloned_types = Vec::new();
+
+    let arms = variants.iter()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let fields = v.data.fields();
+            // struct fields are bound to generated names too, so a field named `f` can't shadow the closure
+            let bindings = (0..fields.len()).map(|i| Ident::from(format!("v{}", i))).collect::<Vec<_>>();
+
+            let mut map_values = Vec::new();
+            let mut ref_values = Vec::new();
+            let mut mut_values = Vec::new();
+            for (field, binding) in fields.iter().zip(bindings.iter()) {
+                let ty = &field.ty;
+                if mentions_ty_param(ty, param) {
+                    let rewrite = |rewrite| rewrite_member(ty, param, name, quote! { #binding }, rewrite, 0)
+                        .unwrap_or_else(|| panic!("EnumFunctor can't rewrite the member `{}` of {}::{}; supported members \
+                                                   are `{}`, {}<{}>, and Option, Box and Vec of those",
+                                                  quote! { #ty }, name, variant_name, param, name, param));
+                    map_values.push(rewrite(Rewrite::Map));
+                    ref_values.push(rewrite(Rewrite::AsRef));
+                    mut_values.push(rewrite(Rewrite::AsMut));
+                }
+                else {
+                    cloned_types.push(field.ty.clone());
+                    map_values.push(quote! { #binding });
+                    ref_values.push(quote! { Clone::clone(#binding) });
+                    mut_values.push(quote! { Clone::clone(#binding) });
+                }
+            }
+            let ref_values2 = mut_values;
+
+            let bindings2 = bindings.clone();
+            let bindings3 = bindings.clone();
+            match v.data {
+                VariantData::Tuple(_) => (
+                    quote! { #name::#variant_name(#(#bindings),*) => #name::#variant_name(#(#map_values),*) },
+                    quote! { #name::#variant_name(#(ref #bindings2),*) => #name::#variant_name(#(#ref_values),*) },
+                    quote! { #name::#variant_name(#(ref mut #bindings3),*) => #name::#variant_name(#(#ref_values2),*) },
+                ),
+                VariantData::Struct(_) => {
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    let field_names2 = field_names.clone();
+                    let field_names3 = field_names.clone();
+                    let field_names4 = field_names.clone();
+                    let field_names5 = field_names.clone();
+                    let field_names6 = field_names.clone();
+                    (
+                        quote! { #name::#variant_name { #(#field_names: #bindings),* } => #name::#variant_name { #(#field_names4: #map_values),* } },
+                        quote! { #name::#variant_name { #(#field_names2: ref #bindings2),* } => #name::#variant_name { #(#field_names5: #ref_values),* } },
+                        quote! { #name::#variant_name { #(#field_names3: ref mut #bindings3),* } => #name::#variant_name { #(#field_names6: #ref_values2),* } },
+                    )
+                }
+                VariantData::Unit => (
+                    quote! { #name::#variant_name => #name::#variant_name },
+                    quote! { #name::#variant_name => #name::#variant_name },
+                    quote! { #name::#variant_name => #name::#variant_name },
+                ),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let map_arms = arms.iter().map(|a| a.0.clone()).collect::<Vec<_>>();
+    let ref_arms = arms.iter().map(|a| a.1.clone()).collect::<Vec<_>>();
+    let mut_arms = arms.iter().map(|a| a.2.clone()).collect::<Vec<_>>();
+    let cloned_types2 = cloned_types.clone();
+
+    let map_doc = format!("Transforms every `{}` held by the enum, keeping the variant and everything else it holds.", param);
+    let as_ref_doc = format!("Borrows every `{}` held by the enum, cloning any other members.", param);
+    let as_mut_doc = format!("Mutably borrows every `{}` held by the enum, cloning any other members.", param);
+
+    // as with the other optional methods, the higher-ranked bounds defer checking `Clone` until
+    // `as_ref` or `as_mut` is called
+    quote! {
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics {
+            #[doc = #map_doc]
+            pub fn map<#mapped>(self, mut f: impl FnMut(#param) -> #mapped) -> #name<#(#lifetimes,)* #mapped> {
+                self.map_with(&mut f)
+            }
+
+            /// Does the work of `map` with a borrowed closure, which recursive members can share.
+            fn map_with<#mapped>(self, f: &mut dyn FnMut(#param) -> #mapped) -> #name<#(#lifetimes4,)* #mapped> {
+                match self {
+                    #(#map_arms),*
+                }
+            }
+
+            #[doc = #as_ref_doc]
+            pub fn as_ref(&self) -> #name<#(#lifetimes2,)* &#param> where #(for<'c> #cloned_types: Clone),* {
+                match *self {
+                    #(#ref_arms),*
+                }
+            }
+
+            #[doc = #as_mut_doc]
+            pub fn as_mut(&mut self) -> #name<#(#lifetimes3,)* &mut #param> where #(for<'c> #cloned_types2: Clone),* {
+                match *self {
+                    #(#mut_arms),*
+                }
+            }
+        }
+    }
+}
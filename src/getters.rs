@@ -305,3 +305,432 @@ pub(crate) fn impl_enum_to_getters(ast: &DeriveInput) -> quote::Tokens {
 
     tokens
 }
+
+/// Gives `Option`-returning getters that convert the data of several single-member variants into
+/// one common type, declared with `#[enum_methods(getter(name = "...", ty = "..."))]` on the enum.
+pub(crate) fn impl_enum_group_to_getters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let getters = enum_methods_lists(&ast.attrs, "getter");
+
+    let function_names = getters.iter()
+        .map(|items| meta_str_value(items, "name").expect("getter(...) requires a name = \"...\""))
+        .map(Ident::from)
+        .collect::<Vec<Ident>>();
+
+    let getter_types = getters.iter()
+        .map(|items| meta_str_value(items, "ty").expect("getter(...) requires a ty = \"...\""))
+        .map(|ty| parse_type(&ty).unwrap_or_else(|e| panic!("invalid getter type `{}`: {}", ty, e)))
+        .collect::<Vec<Ty>>();
+
+    let getter_arms = getters.iter()
+        .zip(getter_types.iter())
+        .zip(function_names.iter())
+        .map(|((items, ty), function_name)| {
+            // the converted variants are listed with variants(...), or marked with
+            // #[enum_methods(getter = "...")] next to each variant, since only the user knows which
+            // data can be converted
+            let mut listed = meta_list_words(items, "variants");
+            listed.extend(variants.iter()
+                .filter(|v| enum_methods_str_values(&v.attrs, "getter").iter().any(|g| function_name == g.as_str()))
+                .map(|v| v.ident.clone()));
+            if listed.is_empty() {
+                panic!("getter `{}` of {} converts no variants; list them with variants(...) or mark them \
+                        with #[enum_methods(getter = \"{}\")]", function_name, name, function_name);
+            }
+
+            let getter_variants = variants.iter()
+                .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() == 1 } else { false })
+                .filter(|v| listed.contains(&v.ident))
+                .map(|v| v.ident.clone())
+                .collect::<Vec<Ident>>();
+
+            if let Some(unknown) = listed.iter().find(|l| !getter_variants.contains(l)) {
+                panic!("getter variant `{}` is not a single-member tuple variant of {}", unknown, name);
+            }
+
+            let conversion = match meta_str_value(items, "via").as_deref() {
+                None | Some("Into") => quote! { Some(::std::convert::Into::into(v.clone())) },
+                Some("TryInto") => quote! { ::std::convert::TryInto::try_into(v.clone()).ok() },
+                Some("as") => quote! { Some(v.clone() as #ty) },
+                Some(via) => panic!("unknown getter conversion `{}`; expected Into, TryInto or as", via),
+            };
+            let conversions = vec!(conversion; getter_variants.len());
+            let getter_names = vec!(name.clone(); getter_variants.len());
+
+            quote! {
+                #(#getter_names::#getter_variants(ref v) => #conversions,)*
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#[allow(unreachable_patterns)]
+            pub fn #function_names(&self) -> Option<#getter_types> {
+                match *self {
+                    #getter_arms
+                    _ => None,
+                }
+            })*
+        }
+    }
+}
+
+/// Gives `Option`-like combinators (`map_*`, `is_*_and`, `*_or`, `*_or_else`, `*_or_default` and
+/// `inspect_*`) for the same variants that get `as_*`/`into_*` getters.
+pub(crate) fn impl_enum_combinators(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! getter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(_) = v.data { true } else { false })
+        };
+    }
+
+    let combinators = getter_filter!()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let snake_name = to_snake_case(&v.ident);
+            let map_name: Ident = format!("map_{}", snake_name).into();
+            let is_and_name: Ident = format!("is_{}_and", snake_name).into();
+            let or_name: Ident = format!("{}_or", snake_name).into();
+            let or_else_name: Ident = format!("{}_or_else", snake_name).into();
+            let or_default_name: Ident = format!("{}_or_default", snake_name).into();
+            let inspect_name: Ident = format!("inspect_{}", snake_name).into();
+
+            let fields = v.data.fields();
+            // the closures are named `func`, which the bindings `a`, `b`, ... can never shadow, and
+            // their types are prefixed so they can't hide the user's types either
+            let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+            let (owned_type, ref_type, owned_value, ref_value) = if fields.len() == 1 {
+                let ty = &fields[0].ty;
+                let arg = &args[0];
+                (quote! { #ty }, quote! { &#ty }, quote! { #arg }, quote! { #arg })
+            } else {
+                let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                let types2 = types.clone();
+                let args2 = args.clone();
+                let args3 = args.clone();
+                (quote! { (#(#types),*) }, quote! { (#(&#types2),*) }, quote! { (#(#args2),*) }, quote! { (#(#args3),*) })
+            };
+            let args2 = args.clone();
+            let owned_pattern = quote! { #name::#variant_name(#(#args),*) };
+            let ref_pattern = quote! { #name::#variant_name(#(ref #args2),*) };
+
+            let mut tokens = quote! {
+                pub fn #map_name<__U, __F: FnOnce(#owned_type) -> __U>(self, func: __F) -> Option<__U> {
+                    match self {
+                        #owned_pattern => Some(func(#owned_value)),
+                        _ => None,
+                    }
+                }
+
+                pub fn #is_and_name<__F: FnOnce(#ref_type) -> bool>(&self, func: __F) -> bool {
+                    match *self {
+                        #ref_pattern => func(#ref_value),
+                        _ => false,
+                    }
+                }
+
+                pub fn #inspect_name<__F: FnOnce(#ref_type)>(&self, func: __F) -> &Self {
+                    if let #ref_pattern = *self {
+                        func(#ref_value);
+                    }
+                    self
+                }
+            };
+
+            // the higher-ranked bound defers checking `Default` until the method is used, since a
+            // plain bound on a concrete type that isn't `Default` is a compile error
+            tokens.append(quote! {
+                pub fn #or_name(self, default: #owned_type) -> #owned_type {
+                    match self {
+                        #owned_pattern => #owned_value,
+                        _ => default,
+                    }
+                }
+
+                pub fn #or_else_name<__F: FnOnce() -> #owned_type>(self, func: __F) -> #owned_type {
+                    match self {
+                        #owned_pattern => #owned_value,
+                        _ => func(),
+                    }
+                }
+
+                pub fn #or_default_name(self) -> #owned_type where for<'d> #owned_type: Default {
+                    match self {
+                        #owned_pattern => #owned_value,
+                        _ => Default::default(),
+                    }
+                }
+            });
+
+            tokens
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code, unreachable_patterns)]
+        impl #name {
+            #(#combinators)*
+        }
+    }
+}
+
+/// Gets the suffixes for the positional getters of a tuple variant, which are either the names
+/// given by `#[enum_methods(fields(...))]` or the position of each member.
+fn positional_getter_suffixes(v: &Variant) -> Vec<String> {
+    let count = v.data.fields().len();
+    match enum_methods_list(&v.attrs, "fields") {
+        Some(items) => {
+            let names = meta_words(items, "fields");
+            if names.len() != count {
+                panic!("fields(...) on variant {} names {} members, but it has {}", v.ident, names.len(), count);
+            }
+            names.iter().map(|n| n.to_string()).collect()
+        }
+        None => (0..count).map(|i| i.to_string()).collect(),
+    }
+}
+
+/// Gives `as_*_N` and `as_*_N_mut` getters for each member of tuple variants with more than 1
+/// member.
+pub(crate) fn impl_enum_positional_as_getters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! getter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() > 1 } else { false })
+        };
+    }
+
+    let getters = getter_filter!()
+        .flat_map(|v| {
+            let suffixes = positional_getter_suffixes(v);
+            v.data.fields().iter().enumerate().zip(suffixes).map(move |((i, f), suffix)| (v, i, f, suffix))
+        })
+        .map(|(v, i, f, suffix)| {
+            let variant_name = &v.ident;
+            let ty = &f.ty;
+            let function_name_str = format!("as_{}_{}", to_snake_case(&v.ident), suffix);
+            let function_name: Ident = function_name_str.clone().into();
+            let function_name_mut: Ident = format!("{}_mut", function_name_str).into();
+            let function_name_mut_str = format!("{}_mut", function_name_str);
+            // only the requested member is bound, every other member is ignored
+            let members = (0..v.data.fields().len())
+                .map(|j| if i == j { quote! { ref v } } else { quote! { _ } })
+                .collect::<Vec<_>>();
+            let members_mut = (0..v.data.fields().len())
+                .map(|j| if i == j { quote! { ref mut v } } else { quote! { _ } })
+                .collect::<Vec<_>>();
+
+            quote! {
+                pub fn #function_name(&self) -> &#ty {
+                    if let #name::#variant_name(#(#members),*) = *self {
+                        v
+                    }
+                    else {
+                        panic!(concat!("called ", #function_name_str, "() on {:?}"), self);
+                    }
+                }
+
+                pub fn #function_name_mut(&mut self) -> &mut #ty {
+                    if let #name::#variant_name(#(#members_mut),*) = *self {
+                        v
+                    }
+                    else {
+                        panic!(concat!("called ", #function_name_mut_str, "() on {:?}"), self);
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#getters)*
+        }
+    }
+}
+
+/// Gives `into_*_N` getters for each member of tuple variants with more than 1 member.
+pub(crate) fn impl_enum_positional_into_getters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! getter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() > 1 } else { false })
+        };
+    }
+
+    let getters = getter_filter!()
+        .flat_map(|v| {
+            let suffixes = positional_getter_suffixes(v);
+            v.data.fields().iter().enumerate().zip(suffixes).map(move |((i, f), suffix)| (v, i, f, suffix))
+        })
+        .map(|(v, i, f, suffix)| {
+            let variant_name = &v.ident;
+            let ty = &f.ty;
+            let function_name_str = format!("into_{}_{}", to_snake_case(&v.ident), suffix);
+            let function_name: Ident = function_name_str.clone().into();
+            let members = (0..v.data.fields().len())
+                .map(|j| if i == j { quote! { v } } else { quote! { _ } })
+                .collect::<Vec<_>>();
+
+            quote! {
+                pub fn #function_name(self) -> #ty {
+                    if let #name::#variant_name(#(#members),*) = self {
+                        v
+                    }
+                    else {
+                        panic!(concat!("called ", #function_name_str, "() on {:?}"), self);
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#getters)*
+        }
+    }
+}
+
+/// Gives `as_*_*` and `as_*_*_mut` getters for the inner variants listed with a type in
+/// `#[enum_methods(flatten(...))]`.
+pub(crate) fn impl_flattened_as_getters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let getters = flattened_variants(ast)
+        .iter()
+        .filter(|f| f.ty.is_some())
+        .map(|f| {
+            let variant_name = &f.outer.ident;
+            let inner_enum = &f.inner_enum;
+            let inner_name = &f.inner;
+            let ty = &f.ty;
+            let function_name_str = format!("as_{}", f.snake_name());
+            let function_name: Ident = function_name_str.clone().into();
+            let function_name_mut_str = format!("{}_mut", function_name_str);
+            let function_name_mut: Ident = function_name_mut_str.clone().into();
+            // the inner enum is matched through the reference to it, and through its box if it has one
+            let inner = if f.boxed { quote! { **inner } } else { quote! { *inner } };
+
+            quote! {
+                #[allow(unreachable_patterns)]
+                pub fn #function_name(&self) -> &#ty {
+                    if let #name::#variant_name(ref inner) = *self {
+                        if let #inner_enum::#inner_name(ref v) = #inner {
+                            return v;
+                        }
+                    }
+                    panic!(concat!("called ", #function_name_str, "() on {:?}"), self);
+                }
+
+                #[allow(unreachable_patterns)]
+                pub fn #function_name_mut(&mut self) -> &mut #ty {
+                    let matches = match *self {
+                        #name::#variant_name(ref inner) => match #inner {
+                            #inner_enum::#inner_name(_) => true,
+                            _ => false,
+                        },
+                        _ => false,
+                    };
+                    if !matches {
+                        panic!(concat!("called ", #function_name_mut_str, "() on {:?}"), self);
+                    }
+                    match *self {
+                        #name::#variant_name(ref mut inner) => match #inner {
+                            #inner_enum::#inner_name(ref mut v) => v,
+                            _ => unreachable!(),
+                        },
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#getters)*
+        }
+    }
+}
+
+/// Gives `into_*_*` getters for the inner variants listed with a type in
+/// `#[enum_methods(flatten(...))]`.
+pub(crate) fn impl_flattened_into_getters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let getters = flattened_variants(ast)
+        .iter()
+        .filter(|f| f.ty.is_some())
+        .map(|f| {
+            let variant_name = &f.outer.ident;
+            let inner_enum = &f.inner_enum;
+            let inner_name = &f.inner;
+            let ty = &f.ty;
+            let function_name_str = format!("into_{}", f.snake_name());
+            let function_name: Ident = function_name_str.clone().into();
+            let inner_ref = if f.boxed { quote! { **inner } } else { quote! { *inner } };
+            let inner = if f.boxed { quote! { *inner } } else { quote! { inner } };
+
+            // the enum is checked by reference first, so it can still be shown when panicking
+            quote! {
+                #[allow(unreachable_patterns)]
+                pub fn #function_name(self) -> #ty {
+                    let matches = match self {
+                        #name::#variant_name(ref inner) => match #inner_ref {
+                            #inner_enum::#inner_name(_) => true,
+                            _ => false,
+                        },
+                        _ => false,
+                    };
+                    if !matches {
+                        panic!(concat!("called ", #function_name_str, "() on {:?}"), self);
+                    }
+                    match self {
+                        #name::#variant_name(inner) => match #inner {
+                            #inner_enum::#inner_name(v) => v,
+                            _ => unreachable!(),
+                        },
+                        _ => unreachable!(),
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#getters)*
+        }
+    }
+}
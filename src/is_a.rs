@@ -131,3 +131,115 @@ pub(crate) fn impl_struct_enum_is_a(ast: &DeriveInput) -> quote::Tokens {
         }
     }
 }
+
+/// Gives implementations of is_* functions for variant groups, declared with
+/// `#[enum_methods(group = "...")]` on each variant of the group.
+pub(crate) fn impl_group_enum_is_a(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    // group names are validated and normalized to snake_case as they are found, so the method and
+    // constant names are always derived from the same spelling
+    let normalize = |group: &str, variant: &Variant| {
+        let valid = group.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+            && group.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            panic!("group name \"{}\" on {}::{} is not a valid identifier", group, name, variant.ident);
+        }
+        to_snake_case(&group)
+    };
+
+    // groups are kept in the order they first appear, along with the spelling they first appear as
+    let mut groups: Vec<(String, String, Vec<&Variant>)> = Vec::new();
+    for variant in variants {
+        for group in enum_methods_str_values(&variant.attrs, "group") {
+            let normalized = normalize(&group, variant);
+            match groups.iter().position(|(_, g, _)| *g == normalized) {
+                Some(i) if groups[i].0 != group => {
+                    panic!("groups \"{}\" and \"{}\" of {} would both generate is_{}()",
+                           groups[i].0, group, name, normalized);
+                }
+                Some(i) => groups[i].2.push(variant),
+                None => groups.push((group, normalized, vec![variant])),
+            }
+        }
+    }
+
+    // the group methods share their namespace with the is_* methods of every variant
+    let variant_function_names = variants.iter()
+        .map(|v| format!("is_{}", to_snake_case(&v.ident)))
+        .collect::<Vec<_>>();
+
+    for (group, normalized, _) in &groups {
+        let function_name = format!("is_{}", normalized);
+        if let Some(i) = variant_function_names.iter().position(|f| *f == function_name) {
+            panic!("group \"{}\" of {} would generate {}(), which is already generated for the variant {}",
+                   group, name, function_name, variants[i].ident);
+        }
+    }
+
+    let function_names = groups.iter()
+        .map(|(_, normalized, _)| format!("is_{}", normalized).into())
+        .collect::<Vec<Ident>>();
+
+    let const_names = groups.iter()
+        .map(|(_, normalized, _)| format!("GROUP_{}", normalized.to_uppercase()).into())
+        .collect::<Vec<Ident>>();
+
+    let group_patterns = groups.iter()
+        .map(|(_, _, vs)| vs.iter().map(|v| variant_wildcard_pattern(name, v)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let group_variant_strs = groups.iter()
+        .map(|(_, _, vs)| vs.iter().map(|v| v.ident.to_string()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(pub const #const_names: &'static [&'static str] = &[#(#group_variant_strs),*];)*
+
+            #(#[allow(unreachable_patterns)]
+            pub fn #function_names(&self) -> bool {
+                match *self {
+                    #(#group_patterns)|* => true,
+                    _ => false,
+                }
+            })*
+        }
+    }
+}
+
+/// Gives `is_*_*` methods for the inner variants listed with `#[enum_methods(flatten(...))]`, which
+/// defer to the `is_*` methods of the inner enum.
+pub(crate) fn impl_flattened_enum_is_a(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let methods = flattened_variants(ast)
+        .iter()
+        .map(|f| {
+            let variant_name = &f.outer.ident;
+            let function_name: Ident = format!("is_{}", f.snake_name()).into();
+            let inner_function_name: Ident = format!("is_{}", to_snake_case(&f.inner)).into();
+            quote! {
+                #[allow(unreachable_patterns)]
+                pub fn #function_name(&self) -> bool {
+                    match *self {
+                        #name::#variant_name(ref v) => v.#inner_function_name(),
+                        _ => false,
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#methods)*
+        }
+    }
+}
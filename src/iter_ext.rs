@@ -0,0 +1,131 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gets the plural of a snake_case name, e.g. `foo` becomes `foos` and `box` becomes `boxes`.
+fn pluralize(name: &str) -> String {
+    if ["s", "x", "z", "ch", "sh"].iter().any(|suffix| name.ends_with(suffix)) {
+        format!("{}es", name)
+    }
+    else {
+        format!("{}s", name)
+    }
+}
+
+/// Gives the `{Enum}IterExt` and `{Enum}IntoIterExt` traits, which filter iterators over the
+/// enum down to the data of one variant.
+pub(crate) fn impl_enum_iter_ext(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let iter_ext: Ident = format!("{}IterExt", name).into();
+    let into_iter_ext: Ident = format!("{}IntoIterExt", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! getter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(_) = v.data { true } else { false })
+        };
+    }
+
+    let methods = getter_filter!()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let function_name: Ident = pluralize(&to_snake_case(&v.ident)).into();
+            let fields = v.data.fields();
+            let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+            let types2 = types.clone();
+            let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+            let args2 = args.clone();
+            let args3 = args.clone();
+            let args4 = args.clone();
+
+            // like the getters, a single member is yielded as-is and several members as a tuple
+            let (ref_type, owned_type, ref_value, owned_value) = if fields.len() == 1 {
+                (quote! { #(&'a #types)* }, quote! { #(#types2)* }, quote! { #(#args)* }, quote! { #(#args2)* })
+            } else {
+                (quote! { (#(&'a #types),*) }, quote! { (#(#types2),*) }, quote! { (#(#args),*) }, quote! { (#(#args2),*) })
+            };
+
+            (quote! {
+                fn #function_name(self) -> ::std::iter::FilterMap<Self, fn(&'a #name) -> Option<#ref_type>> {
+                    self.filter_map(|value| match *value {
+                        #name::#variant_name(#(ref #args3),*) => Some(#ref_value),
+                        _ => None,
+                    })
+                }
+            },
+             quote! {
+                fn #function_name(self) -> ::std::iter::FilterMap<Self, fn(#name) -> Option<#owned_type>> {
+                    self.filter_map(|value| match value {
+                        #name::#variant_name(#(#args4),*) => Some(#owned_value),
+                        _ => None,
+                    })
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let ref_methods = methods.iter().map(|m| m.0.clone()).collect::<Vec<_>>();
+    let owned_methods = methods.iter().map(|m| m.1.clone()).collect::<Vec<_>>();
+
+    let variant_count = Ident::from(variants.len());
+    let variant_name_strs = variants.iter()
+        .map(|v| v.ident.to_string())
+        .collect::<Vec<String>>();
+    let variant_patterns = variants.iter()
+        .map(|v| variant_wildcard_pattern(name, v))
+        .collect::<Vec<_>>();
+    let variant_indices = (0..variants.len()).map(Ident::from).collect::<Vec<_>>();
+    let variant_count2 = variant_count.clone();
+    let variant_name_strs2 = variant_name_strs.clone();
+    let variant_patterns2 = variant_patterns.clone();
+    let variant_indices2 = variant_indices.clone();
+
+    let mut tokens = quote! {
+        #[allow(unreachable_patterns)]
+        #vis trait #iter_ext<'a>: Iterator<Item = &'a #name> + Sized {
+            #(#ref_methods)*
+
+            /// Counts the values of each variant, in declaration order.
+            fn count_by_variant(self) -> [(&'static str, usize); #variant_count] {
+                let mut counts = [#((#variant_name_strs, 0)),*];
+                for value in self {
+                    let index = match *value {
+                        #(#variant_patterns => #variant_indices),*
+                    };
+                    counts[index].1 += 1;
+                }
+                counts
+            }
+        }
+    };
+
+    tokens.append(quote! {
+        #[allow(unreachable_patterns)]
+        #vis trait #into_iter_ext: Iterator<Item = #name> + Sized {
+            #(#owned_methods)*
+
+            /// Counts the values of each variant, in declaration order.
+            fn count_by_variant(self) -> [(&'static str, usize); #variant_count2] {
+                let mut counts = [#((#variant_name_strs2, 0)),*];
+                for value in self {
+                    let index = match value {
+                        #(#variant_patterns2 => #variant_indices2),*
+                    };
+                    counts[index].1 += 1;
+                }
+                counts
+            }
+        }
+
+        impl<'a, I: Iterator<Item = &'a #name>> #iter_ext<'a> for I {}
+
+        impl<I: Iterator<Item = #name>> #into_iter_ext for I {}
+    });
+
+    tokens
+}
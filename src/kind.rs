@@ -0,0 +1,335 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gets the name of the generated kind enum, which is either given by
+/// `#[enum_methods(kind(name = "..."))]` or defaults to `{Enum}Kind`.
+pub(crate) fn kind_name(ast: &DeriveInput) -> Ident {
+    enum_methods_list(&ast.attrs, "kind")
+        .and_then(|items| meta_str_value(items, "name"))
+        .unwrap_or_else(|| format!("{}Kind", ast.ident))
+        .into()
+}
+
+/// Gives the fieldless kind enum mirroring the variants, along with the `kind()` method.
+pub(crate) fn impl_enum_kind(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let kind = kind_name(ast);
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let extra_derives = enum_methods_list(&ast.attrs, "kind")
+        .map(|items| meta_list_words(items, "derive"))
+        .unwrap_or_default();
+
+    let variant_names = variants.iter()
+        .map(|v| v.ident.clone())
+        .collect::<Vec<Ident>>();
+
+    let variant_count = Ident::from(variants.len());
+
+    let all_kinds = vec!(kind.clone(); variant_names.len());
+
+    let variant_patterns = variants.iter()
+        .map(|v| variant_wildcard_pattern(name, v))
+        .collect::<Vec<_>>();
+
+    let kind_names = vec!(kind.clone(); variant_names.len());
+
+    let function_names = variants.iter()
+        .map(|v| format!("is_{}", to_snake_case(&v.ident)).into())
+        .collect::<Vec<Ident>>();
+
+    let variant_name_strs = variants.iter()
+        .map(|v| v.ident.to_string())
+        .collect::<Vec<String>>();
+
+    let variant_names2 = variant_names.clone();
+    let variant_names3 = variant_names.clone();
+    let variant_names4 = variant_names.clone();
+    let variant_names5 = variant_names.clone();
+    let kind_names2 = kind_names.clone();
+    let kind_names3 = kind_names.clone();
+
+    quote! {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug, #(#extra_derives),*)]
+        #vis enum #kind {
+            #(#variant_names),*
+        }
+
+        #[allow(dead_code)]
+        impl #kind {
+            /// The number of variants.
+            pub const COUNT: usize = #variant_count;
+
+            /// Every kind, in declaration order.
+            pub const ALL: [#kind; #variant_count] = [#(#all_kinds::#variant_names2),*];
+
+            /// Gets the position of this kind in declaration order.
+            pub fn index(self) -> usize {
+                self as usize
+            }
+
+            /// Gets the name of the variant this kind mirrors.
+            pub fn name(self) -> &'static str {
+                match self {
+                    #(#kind_names::#variant_names3 => #variant_name_strs),*
+                }
+            }
+
+            #(pub fn #function_names(self) -> bool {
+                self == #kind_names2::#variant_names4
+            })*
+        }
+
+        #[allow(dead_code)]
+        impl #name {
+            pub fn kind(&self) -> #kind {
+                match *self {
+                    #(#variant_patterns => #kind_names3::#variant_names5),*
+                }
+            }
+        }
+    }
+}
+
+/// Gives a fixed-size map keyed by the kind enum, which is generated by `impl_enum_kind`.
+pub(crate) fn impl_enum_kind_map(ast: &DeriveInput) -> quote::Tokens {
+    let vis = &ast.vis;
+    let kind = kind_name(ast);
+    let map: Ident = format!("{}Map", kind).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let variant_names = variants.iter()
+        .map(|v| v.ident.clone())
+        .collect::<Vec<Ident>>();
+
+    let variant_count = Ident::from(variants.len());
+
+    let kind_names = vec!(kind.clone(); variant_names.len());
+
+    quote! {
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        #vis struct #map<V> {
+            values: [V; #variant_count],
+        }
+
+        #[allow(dead_code)]
+        impl<V> #map<V> {
+            /// Creates a map holding a value for every kind.
+            pub fn from_fn<F: FnMut(#kind) -> V>(mut f: F) -> Self {
+                #map {
+                    values: [#(f(#kind_names::#variant_names)),*],
+                }
+            }
+
+            pub fn get(&self, kind: #kind) -> &V {
+                &self.values[kind.index()]
+            }
+
+            pub fn get_mut(&mut self, kind: #kind) -> &mut V {
+                &mut self.values[kind.index()]
+            }
+
+            /// Replaces the value for the given kind, returning the old value.
+            pub fn insert(&mut self, kind: #kind, value: V) -> V {
+                ::std::mem::replace(&mut self.values[kind.index()], value)
+            }
+
+            pub fn iter(&self) -> impl Iterator<Item = (#kind, &V)> {
+                #kind::ALL.iter().cloned().zip(self.values.iter())
+            }
+
+            pub fn iter_mut(&mut self) -> impl Iterator<Item = (#kind, &mut V)> {
+                #kind::ALL.iter().cloned().zip(self.values.iter_mut())
+            }
+        }
+
+        impl<V: Default> Default for #map<V> {
+            fn default() -> Self {
+                #map::from_fn(|_| V::default())
+            }
+        }
+
+        impl<V> ::std::ops::Index<#kind> for #map<V> {
+            type Output = V;
+
+            fn index(&self, kind: #kind) -> &V {
+                self.get(kind)
+            }
+        }
+
+        impl<V> ::std::ops::IndexMut<#kind> for #map<V> {
+            fn index_mut(&mut self, kind: #kind) -> &mut V {
+                self.get_mut(kind)
+            }
+        }
+    }
+}
+
+/// Gives a bit set of the kind enum, which is generated by `impl_enum_kind`.
+pub(crate) fn impl_enum_kind_set(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let kind = kind_name(ast);
+    let set: Ident = format!("{}Set", kind).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    // one bit per variant, with at least one word so that even an empty enum gets a valid set
+    let word_count = variants.len().div_ceil(64).max(1);
+    let all_words = (0..word_count)
+        .map(|i| match variants.len().saturating_sub(i * 64) {
+            n if n >= 64 => u64::MAX,
+            n => (1u64 << n) - 1,
+        })
+        .collect::<Vec<u64>>();
+    let word_count = Ident::from(word_count);
+
+    let mut tokens = quote! {
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+        #vis struct #set {
+            bits: [u64; #word_count],
+        }
+
+        #[allow(dead_code)]
+        impl #set {
+            /// The set containing no kinds.
+            pub const EMPTY: #set = #set { bits: [0; #word_count] };
+
+            /// The set containing every kind.
+            pub const ALL: #set = #set { bits: [#(#all_words),*] };
+
+            pub fn new() -> Self {
+                #set::EMPTY
+            }
+
+            /// Adds a kind to the set, returning whether it was not already present.
+            pub fn insert(&mut self, kind: #kind) -> bool {
+                let present = self.contains_kind(kind);
+                self.bits[kind.index() / 64] |= 1 << (kind.index() % 64);
+                !present
+            }
+
+            /// Removes a kind from the set, returning whether it was present.
+            pub fn remove(&mut self, kind: #kind) -> bool {
+                let present = self.contains_kind(kind);
+                self.bits[kind.index() / 64] &= !(1 << (kind.index() % 64));
+                present
+            }
+
+            /// Checks whether the variant of the given value is in the set.
+            pub fn contains(&self, value: &#name) -> bool {
+                self.contains_kind(value.kind())
+            }
+
+            pub fn contains_kind(&self, kind: #kind) -> bool {
+                self.bits[kind.index() / 64] & (1 << (kind.index() % 64)) != 0
+            }
+
+            pub fn len(&self) -> usize {
+                self.bits.iter().map(|word| word.count_ones() as usize).sum()
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.bits.iter().all(|&word| word == 0)
+            }
+
+            /// Iterates over the kinds in the set, in declaration order.
+            pub fn iter(&self) -> impl Iterator<Item = #kind> {
+                let set = *self;
+                #kind::ALL.iter().cloned().filter(move |&kind| set.contains_kind(kind))
+            }
+        }
+    };
+
+    tokens.append(quote! {
+        impl ::std::fmt::Debug for #set {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl From<#kind> for #set {
+            fn from(kind: #kind) -> Self {
+                let mut set = #set::EMPTY;
+                set.insert(kind);
+                set
+            }
+        }
+
+        impl ::std::iter::FromIterator<#kind> for #set {
+            fn from_iter<I: IntoIterator<Item = #kind>>(iter: I) -> Self {
+                let mut set = #set::EMPTY;
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl ::std::iter::Extend<#kind> for #set {
+            fn extend<I: IntoIterator<Item = #kind>>(&mut self, iter: I) {
+                for kind in iter {
+                    self.insert(kind);
+                }
+            }
+        }
+
+    });
+
+    tokens.append(quote! {
+        impl ::std::ops::BitOr for #set {
+            type Output = #set;
+
+            fn bitor(mut self, other: #set) -> #set {
+                self |= other;
+                self
+            }
+        }
+
+        impl ::std::ops::BitOrAssign for #set {
+            fn bitor_assign(&mut self, other: #set) {
+                for (word, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+                    *word |= *other;
+                }
+            }
+        }
+
+        impl ::std::ops::BitAnd for #set {
+            type Output = #set;
+
+            fn bitand(mut self, other: #set) -> #set {
+                self &= other;
+                self
+            }
+        }
+
+        impl ::std::ops::BitAndAssign for #set {
+            fn bitand_assign(&mut self, other: #set) {
+                for (word, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+                    *word &= *other;
+                }
+            }
+        }
+
+        impl ::std::ops::Sub for #set {
+            type Output = #set;
+
+            fn sub(mut self, other: #set) -> #set {
+                for (word, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+                    *word &= !*other;
+                }
+                self
+            }
+        }
+    });
+
+    tokens
+}
@@ -85,7 +85,7 @@ fn main() {
 
 # Requirements and gotchas
 
-Right now, `enum-methods` has four derivable options:
+Right now, `enum-methods` has the following derivable options:
 
 * `EnumAsGetters` for generating `as_*` methods, which return a reference.
 * `EnumIntoGetters` for generating `into_*` methods, which consume the enum
@@ -94,6 +94,44 @@ Right now, `enum-methods` has four derivable options:
    the data held by the variant.
 * `EnumIsA` for generating `is_*` methods, which return a boolean indicating
    whether the enum matches that variant.
+* `EnumCombinators` for generating `Option`-like `map_*`, `is_*_and`, `*_or`,
+  `*_or_else`, `*_or_default` and `inspect_*` methods.
+* `EnumConstructors` for generating `new_*` associated functions, which
+  construct a tuple or struct variant.
+* `EnumConversions` for generating `From` and `TryFrom` implementations between
+  the enum and the data held by its variants.
+* `EnumPayloadEq` for generating `*_eq` methods, which compare the data held by
+  a variant without constructing an enum.
+* `EnumSetters` for generating `set_*`, `replace_*` and `set_variant_*`
+  methods, which overwrite the data held by a tuple variant.
+* `EnumFieldAccessors` for generating accessors and setters for each field of
+  a struct variant.
+* `EnumSharedFields` for generating accessors for fields shared by several
+  struct variants.
+* `EnumBuilders` for generating builders for struct variants.
+* `EnumPayloads` for generating a standalone struct holding the data of a
+  variant.
+* `EnumViews` for generating `*Ref<'a>` and `*Mut<'a>` enums, which mirror the
+  enum with borrowed data.
+* `EnumFunctor` for generating `map`, `as_ref` and `as_mut` methods on enums
+  with a type parameter.
+* `EnumVisitor` for generating `*Visitor` and `*VisitorMut` traits along with
+  `accept` and `accept_mut` methods.
+* `EnumFold` for generating a `fold` method, which handles every variant with
+  its own closure.
+* `EnumIterExt` for generating iterator extension traits, which select the
+  data of one variant.
+* `EnumOptionExt` for generating a `*OptionExt` trait, which gives the getters
+  through an `Option` or `Result` holding the enum.
+* `EnumPartition` for generating a `*Partition` struct, which collects values
+  into one `Vec` per variant.
+* `EnumByVariant` for generating a `same_variant` method and a `*ByVariant`
+  wrapper, which compare values by their variant alone.
+* `EnumKind` for generating a fieldless `*Kind` enum with one variant for
+  each variant of the enum, along with a `kind()` method.
+* `EnumKindMap` for generating a fixed-size `*KindMap<V>` holding one value
+  per kind.
+* `EnumKindSet` for generating a `*KindSet` bit set of kinds.
 
 `EnumAsGetters`, `EnumIntoGetters`, and `EnumToGetters` have some limitations.
 
@@ -105,8 +143,205 @@ Right now, `enum-methods` has four derivable options:
 Furthermore, `EnumToGetters` is *only* for enums whose variants implement
 `Clone`. There is not yet support for th
 
+For tuple variants with more than 1 member, `EnumAsGetters` and
+`EnumIntoGetters` also generate getters for each member by position: a variant
+`Tup(i32, String)` gets `as_tup_0()`, `as_tup_0_mut()` and `into_tup_0()`,
+and likewise for `_1`. The positions can be named by marking the variant with
+`#[enum_methods(fields(id, name))]`, which generates `as_tup_id()`,
+`as_tup_name()` and so on instead.
+
+A single-member variant holding another enum can be flattened by listing the
+variants of the inner enum, since a derive can't see the inner enum itself.
+For example, marking `Literal(Literal)` with
+`#[enum_methods(flatten(Int = "i64", Nil))]` generates `is_literal_int()` and
+`is_literal_nil()` with `EnumIsA`, which call the inner `is_*` methods, so the
+inner enum must derive `EnumIsA` too. Variants given with a type also get
+`as_literal_int()` and `as_literal_int_mut()` with `EnumAsGetters` and
+`into_literal_int()` with `EnumIntoGetters`, which panic like the other
+getters. The inner enum may also be held in a `Box`, e.g. `Literal(Box<Literal>)`.
+
+`EnumToGetters` can also generate getters which convert the data of several
+single-member variants into one common type, returning `None` for any other
+variant. For example,
+`#[enum_methods(getter(name = "as_number", ty = "f64", via = "as", variants(Int, Float)))]`
+on the enum generates `as_number(&self) -> Option<f64>`. The `via` conversion
+may be `Into` (the default), `TryInto` or `as`. Instead of `variants(...)`,
+each converted variant may be marked with `#[enum_methods(getter = "as_number")]`;
+one of the two is required, since only variants whose data converts to the
+type can be listed.
+
+`EnumCombinators` generates its methods for the same variants as the getters,
+without panicking. For a variant `Foo(T)`, these are:
+
+* `map_foo(self, f: FnOnce(T) -> U) -> Option<U>`
+* `is_foo_and(&self, f: FnOnce(&T) -> bool) -> bool`
+* `foo_or(self, default: T) -> T`
+* `foo_or_else(self, f: FnOnce() -> T) -> T`
+* `foo_or_default(self) -> T`, which may only be called if `T: Default`
+* `inspect_foo(&self, f: FnOnce(&T)) -> &Self`
+
+Variants with more than one member use tuples in place of `T` and `&T`, like
+their getters.
+
+`EnumConstructors` generates a `new_*` function for every tuple and struct
+variant, taking one `impl Into<T>` argument per member, e.g.
+`MyEnum::new_some_struct(foo, bar)`. Tuple variants with more than one member
+also get a `new_*_from` function taking all members as one tuple. Unit
+variants are skipped.
+
+`EnumConversions` implements `From<T> for MyEnum` and `TryFrom<MyEnum> for T`
+for every variant with exactly 1 member of type `T`, where the `TryFrom` error
+gives back the enum. Since a type can only be converted into one variant,
+variants sharing a type are skipped unless one of them is marked with
+`#[enum_methods(from)]`.
+
+`EnumPayloadEq` generates `foo_eq(&self, other: &U) -> bool` for every variant
+`Foo(T)` where `T: PartialEq<U>`, giving `false` for other variants. Since `U`
+may be unsized, borrowed types can be compared directly, e.g.
+`tok.ident_eq("fn")` for `Ident(String)`. Marking the enum with
+`#[enum_methods(payload_eq)]` also implements `PartialEq<T> for MyEnum` for
+every such type `T` which is held by only one variant, so `my_enum == 42` works.
+
+`EnumSetters` generates three methods for every tuple variant `Foo(T)`:
+
+* `set_foo(&mut self, value: T) -> Result<(), T>` overwrites the data if the
+  enum is a `Foo`, and gives the value back otherwise.
+* `replace_foo(&mut self, value: T) -> Option<T>` switches the enum to
+  `Foo(value)`, returning the old data if it was already a `Foo`.
+* `set_variant_foo(&mut self, value: T)` switches the enum to `Foo(value)`.
+
+Variants with more than one member take and return their data as a tuple.
+
+If a unit variant is marked with `#[enum_methods(default)]`, `EnumSetters`
+also implements `Default` as that variant and generates
+`take_foo(&mut self) -> Option<T>` for every tuple variant, which takes the
+data out of a `Foo` and leaves the default variant in its place.
+
+`EnumFieldAccessors` generates three methods for every field of every struct
+variant, named after the variant and the field. For a variant
+`SomeStruct { foo: i32 }`, these are `some_struct_foo(&self) -> Option<&i32>`,
+`some_struct_foo_mut(&mut self) -> Option<&mut i32>`, and
+`set_some_struct_foo(&mut self, value: i32) -> Result<(), i32>`, which gives
+the value back if the enum is a different variant.
+
+`EnumSharedFields` looks for fields with the same name and type in more than
+one struct variant. A field found in every variant, such as `span: Span`, gets
+`span(&self) -> &Span` and `span_mut(&mut self) -> &mut Span`, while a field
+found in only some variants gets accessors returning an `Option`. Fields whose
+type differs between variants are skipped. To only generate accessors for some
+fields, list them with `#[enum_methods(shared(span, id))]` on the enum.
+
+`EnumBuilders` generates a builder for every struct variant marked with
+`#[enum_methods(builder)]`. For a variant `SomeStruct { foo: i32 }`,
+`MyEnum::some_struct_builder()` returns a `MyEnumSomeStructBuilder` with a
+`foo(impl Into<i32>)` setter and a `build()` method returning the enum. Fields
+of type `Option<T>` and fields marked with `#[enum_methods(default)]` are
+filled with `Default::default()` if they are not set. Every other field is
+tracked by a type parameter of the builder, so `build()` only exists once all
+of them are set and a missing field is a compile error.
+
+`EnumPayloads` generates a struct for every variant marked with
+`#[enum_methods(payload)]`, or for every tuple and struct variant if the enum
+itself is marked. A variant `Tup(i32, String)` gets a tuple struct
+`MyEnumTup(pub i32, pub String)` and a borrowed view
+`MyEnumTupRef<'a>(pub &'a i32, pub &'a String)`, while struct variants get
+structs with the same field names. The enum implements `From<MyEnumTup>`,
+`MyEnumTup` implements `TryFrom<MyEnum>`, and the enum gets the panicking
+getters `into_tup_payload()` and `as_tup_payload()`, so it must also derive
+`Debug`. Derives for the generated struct can be given with
+`#[enum_methods(payload(derive(Debug, PartialEq)))]`.
+
+`EnumViews` generates `MyEnumRef<'a>` and `MyEnumMut<'a>`, which have the same
+variants as `MyEnum` but hold `&'a` and `&'a mut` references to its data. They
+are created with `as_view(&self)` and `as_view_mut(&mut self)`, and
+`MyEnumRef` has a `to_owned()` method cloning the data back into a `MyEnum`,
+which may only be called if every member implements `Clone`. At least one
+variant must hold data.
+
+`EnumFunctor` is for enums with exactly one type parameter, such as
+`enum Annotated<T> { Plain(T), Tagged(String, T) }`. It generates
+`map(self, f: impl FnMut(T) -> U) -> Annotated<U>`, which applies `f` to every
+`T` held by the enum, and `as_ref(&self) -> Annotated<&T>` and
+`as_mut(&mut self) -> Annotated<&mut T>`. Besides `T` itself, members may be
+`Annotated<T>` or an `Option`, `Box` or `Vec` of any of these, e.g.
+`Node(Box<Annotated<T>>, Vec<Option<T>>)`. Members which don't mention `T` are
+moved by `map` and cloned by `as_ref` and `as_mut`, which may only be called if
+those members implement `Clone`. The type parameter can't have any bounds.
+
+`EnumVisitor` generates a `MyEnumVisitor` trait with an `Output` type and one
+`visit_*` method per variant, which takes the variant's data by reference.
+Tuple members are passed as `a`, `b`, and so on, while struct fields are
+passed by name. `MyEnumVisitorMut` is the same, but with mutable references.
+`my_enum.accept(&mut visitor)` and `my_enum.accept_mut(&mut visitor)` call the
+method matching the variant.
+
+`EnumFold` generates `fold(self, ...)`, which takes one closure per variant in
+declaration order and calls the one matching the variant with its data, e.g.
+`my_enum.fold(|foo| ..., |(a, b)| ..., || ...)`. Like the getters, variants
+with a single member pass it as-is, variants with several members pass them as
+a tuple (struct fields in declaration order), and unit variants pass nothing.
+Since every closure is required, adding a variant breaks every `fold` call
+until it is handled.
+
+`EnumIterExt` generates `MyEnumIterExt`, implemented for every iterator over
+`&MyEnum`, and `MyEnumIntoIterExt`, implemented for every iterator over
+`MyEnum`. They have one method per variant which has a getter, named with the
+plural of the variant, e.g. `values.iter().foos()` yields the `&T` held by
+every `Foo` and `values.into_iter().foos()` yields the `T`. Both traits also
+have `count_by_variant()`, which returns the number of values of each variant
+as `(name, count)` pairs in declaration order.
+
+`EnumOptionExt` generates `MyEnumOptionExt`, implemented for `Option<MyEnum>`,
+`Option<&MyEnum>`, `Option<&mut MyEnum>` and the matching `Result<_, E>`
+types. It has `is_*` for every variant, plus `as_*` and `into_*` for every
+variant with exactly 1 member, where `None` and `Err` count as any other
+variant, e.g. `map.get(key).is_foo()` or `map.get(key).as_foo()`. `into_*`
+returns the data as held by the option, so it gives `Option<T>` for
+`Option<MyEnum>`, `Option<&T>` for `Option<&MyEnum>` and `Option<&mut T>` for
+`Option<&mut MyEnum>`. Since methods of `Option` and `Result` take precedence,
+variants which would generate one of them (e.g. `is_ok()` for `Ok`) are
+rejected unless every method is prefixed with
+`#[enum_methods(option_ext(prefix = "status"))]`, giving `is_status_ok()` and
+so on.
+
+`EnumPartition` generates `MyEnumPartition`, which implements `Default`,
+`FromIterator<MyEnum>` and `Extend<MyEnum>`. It has a public field for every
+variant, named after the variant, holding the data of every collected value of
+that variant in a `Vec` (several members are stored as a tuple), e.g.
+`values.into_iter().collect::<MyEnumPartition>().foo`. Fields named after a
+keyword get an underscore appended, e.g. `type_`. Unit variants are only
+counted, in a `*_count: usize` field. Extra derives can be added with
+`#[enum_methods(partition(derive(Debug, PartialEq)))]` on the enum.
+
 `EnumIsA` is much simpler than the previous; it simply adds `is_*`
 methods returning a boolean for whether the variant matches or not.
+Variants can also be put into groups with `#[enum_methods(group = "numeric")]`
+(which may be repeated), generating an `is_numeric()` method matching every
+variant in the group and a `GROUP_NUMERIC` constant listing their names.
+
+`EnumByVariant` generates `same_variant(&self, other: &Self) -> bool`, which
+ignores the data held by both values, and `by_variant()`, which wraps a
+reference in `MyEnumByVariant<'a>(pub &'a MyEnum)`. The wrapper implements
+`Eq`, `Hash` and `Ord` (in declaration order) on the variant alone, so values
+can be grouped or deduplicated by variant in a `HashSet` or `BTreeMap` even if
+their data is not `Hash`, e.g. `f64`.
+
+`EnumKind` generates a `Copy`, `Eq`, `Hash`, `Ord` and `Debug` enum named
+after the original enum with `Kind` appended (e.g. `MyEnumKind`), holding a
+unit variant for every tuple, struct and unit variant. The kind has `is_*`
+methods, `index()`, `name()`, and `COUNT`/`ALL` constants. Its name and any
+extra derives can be set with
+`#[enum_methods(kind(name = "MyKind", derive(Serialize)))]` on the enum.
+
+`EnumKindMap` generates a `*KindMap<V>` (e.g. `MyEnumKindMap<V>`) backed by an
+array indexed by kind, so it always holds exactly one value per variant. It
+has `from_fn`, `get`, `get_mut`, `insert`, `iter` and `iter_mut`, and can be
+indexed by kind. Enums deriving `EnumKindMap` must also derive `EnumKind`.
+
+`EnumKindSet` generates a `Copy` bit set of kinds (e.g. `MyEnumKindSet`) with
+`insert`, `remove`, `contains(&MyEnum)`, `contains_kind`, `iter`, `EMPTY` and
+`ALL` constants, and the `|`, `&` and `-` operators. Enums deriving
+`EnumKindSet` must also derive `EnumKind`.
 
 **For all generated methods, all names are automatically converted to
 snake_case**.
@@ -123,54 +358,274 @@ extern crate proc_macro;
 extern crate quote;
 extern crate syn;
 
+mod builders;
+mod by_variant;
+mod constructors;
+mod conversions;
+mod fields;
+mod fold;
+mod functor;
 mod getters;
 mod is_a;
+mod iter_ext;
+mod kind;
+mod option_ext;
+mod partition;
+mod payload_eq;
+mod payloads;
+mod setters;
 mod util;
-
+mod views;
+mod visitor;
+
+use builders::*;
+use by_variant::*;
+use constructors::*;
+use conversions::*;
+use fields::*;
+use fold::*;
+use functor::*;
 use getters::*;
 use is_a::*;
+use iter_ext::*;
+use kind::*;
+use option_ext::*;
+use partition::*;
+use payload_eq::*;
+use payloads::*;
+use setters::*;
+use views::*;
+use visitor::*;
 use proc_macro::TokenStream;
 use syn::*;
 
 // TODO : map types for what a reference should return in its getter
 // e.g. String -> &str in the getter
 
-#[proc_macro_derive(EnumAsGetters)]
+#[proc_macro_derive(EnumAsGetters, attributes(enum_methods))]
 #[doc(hidden)]
 pub fn enum_as_getters(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = parse_derive_input(&s).unwrap();
-    let getters = impl_enum_as_getters(&ast);
+    let mut getters = impl_enum_as_getters(&ast);
+    getters.append(impl_enum_positional_as_getters(&ast));
+    getters.append(impl_flattened_as_getters(&ast));
     //panic!("{:#?}", getters);
     getters.parse().unwrap()
 }
 
-#[proc_macro_derive(EnumIntoGetters)]
+#[proc_macro_derive(EnumIntoGetters, attributes(enum_methods))]
 #[doc(hidden)]
 pub fn enum_into_getters(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = parse_derive_input(&s).unwrap();
-    let getters = impl_enum_into_getters(&ast);
+    let mut getters = impl_enum_into_getters(&ast);
+    getters.append(impl_enum_positional_into_getters(&ast));
+    getters.append(impl_flattened_into_getters(&ast));
     getters.parse().unwrap()
 }
 
-#[proc_macro_derive(EnumToGetters)]
+#[proc_macro_derive(EnumToGetters, attributes(enum_methods))]
 #[doc(hidden)]
 pub fn enum_to_getters(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = parse_derive_input(&s).unwrap();
-    let getters = impl_enum_to_getters(&ast);
+    let mut getters = impl_enum_to_getters(&ast);
+    getters.append(impl_enum_group_to_getters(&ast));
     getters.parse().unwrap()
 }
 
-#[proc_macro_derive(EnumIsA)]
+#[proc_macro_derive(EnumCombinators)]
+#[doc(hidden)]
+pub fn enum_combinators(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let combinators = impl_enum_combinators(&ast);
+    combinators.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumConstructors)]
+#[doc(hidden)]
+pub fn enum_constructors(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let constructors = impl_enum_constructors(&ast);
+    constructors.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumBuilders, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_builders(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let builders = impl_enum_builders(&ast);
+    builders.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumConversions, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_conversions(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let conversions = impl_enum_conversions(&ast);
+    conversions.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumFieldAccessors)]
+#[doc(hidden)]
+pub fn enum_field_accessors(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let accessors = impl_enum_field_accessors(&ast);
+    accessors.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumSharedFields, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_shared_fields(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let accessors = impl_enum_shared_fields(&ast);
+    accessors.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumIsA, attributes(enum_methods))]
 #[doc(hidden)]
 pub fn enum_is_a(input: TokenStream) -> TokenStream {
     let s = input.to_string();
     let ast = parse_derive_input(&s).unwrap();
     let mut gen = impl_enum_is_a(&ast);
-    gen.append(&mut impl_struct_enum_is_a(&ast));
-    gen.append(&mut impl_unit_enum_is_a(&ast));
+    gen.append(impl_struct_enum_is_a(&ast));
+    gen.append(impl_unit_enum_is_a(&ast));
+    gen.append(impl_group_enum_is_a(&ast));
+    gen.append(impl_flattened_enum_is_a(&ast));
     gen.parse().unwrap()
 }
 
+
+#[proc_macro_derive(EnumKind, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_kind(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let kind = impl_enum_kind(&ast);
+    kind.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumKindMap, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_kind_map(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let map = impl_enum_kind_map(&ast);
+    map.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumKindSet, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_kind_set(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let set = impl_enum_kind_set(&ast);
+    set.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumSetters, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_setters(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let mut setters = impl_enum_setters(&ast);
+    setters.append(impl_enum_take(&ast));
+    setters.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumPayloads, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_payloads(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let payloads = impl_enum_payloads(&ast);
+    payloads.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumViews)]
+#[doc(hidden)]
+pub fn enum_views(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let views = impl_enum_views(&ast);
+    views.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumFunctor)]
+#[doc(hidden)]
+pub fn enum_functor(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let functor = impl_enum_functor(&ast);
+    functor.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumVisitor)]
+#[doc(hidden)]
+pub fn enum_visitor(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let visitor = impl_enum_visitor(&ast);
+    visitor.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumFold)]
+#[doc(hidden)]
+pub fn enum_fold(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let fold = impl_enum_fold(&ast);
+    fold.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumIterExt)]
+#[doc(hidden)]
+pub fn enum_iter_ext(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let iter_ext = impl_enum_iter_ext(&ast);
+    iter_ext.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumPartition, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_partition(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let partition = impl_enum_partition(&ast);
+    partition.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumPayloadEq, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_payload_eq(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let payload_eq = impl_enum_payload_eq(&ast);
+    payload_eq.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumByVariant)]
+#[doc(hidden)]
+pub fn enum_by_variant(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let by_variant = impl_enum_by_variant(&ast);
+    by_variant.parse().unwrap()
+}
+
+#[proc_macro_derive(EnumOptionExt, attributes(enum_methods))]
+#[doc(hidden)]
+pub fn enum_option_ext(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = parse_derive_input(&s).unwrap();
+    let option_ext = impl_enum_option_ext(&ast);
+    option_ext.parse().unwrap()
+}
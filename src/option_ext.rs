@@ -0,0 +1,146 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `{Enum}OptionExt`, which provides `is_*`, `as_*` and `into_*` methods through an `Option` or
+/// `Result` holding the enum by value, by reference or by mutable reference.
+pub(crate) fn impl_enum_option_ext(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let trait_name: Ident = format!("{}OptionExt", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    // like the getters, only tuple variants with exactly one member can be borrowed or moved out
+    let getter_variants = variants.iter()
+        .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() == 1 } else { false })
+        .collect::<Vec<_>>();
+
+    // `Option` and `Result` methods take precedence over trait methods, so any variant which would
+    // shadow one needs every method name to be prefixed with option_ext(prefix = "...")
+    const RESERVED: &[&str] = &[
+        "is_some", "is_none", "is_some_and", "is_none_or", "is_ok", "is_err", "is_ok_and", "is_err_and",
+        "as_ref", "as_mut", "as_deref", "as_deref_mut", "as_slice", "as_mut_slice", "as_pin_ref", "as_pin_mut",
+        "into_iter", "into_ok", "into_err",
+    ];
+    let prefix = enum_methods_list(&ast.attrs, "option_ext")
+        .and_then(|items| meta_str_value(items, "prefix"))
+        .map(|prefix| format!("{}_", prefix))
+        .unwrap_or_default();
+    let method_name = |kind: &str, v: &Variant| -> Ident {
+        let method_name = format!("{}_{}{}", kind, prefix, to_snake_case(&v.ident));
+        if RESERVED.contains(&method_name.as_str()) {
+            panic!("{}::{} would generate {}(), which is already a method of Option or Result; \
+                    prefix the methods with #[enum_methods(option_ext(prefix = \"...\"))]",
+                   name, v.ident, method_name);
+        }
+        method_name.into()
+    };
+
+    let is_names = variants.iter()
+        .map(|v| method_name("is", v))
+        .collect::<Vec<Ident>>();
+    let patterns = variants.iter()
+        .map(|v| variant_wildcard_pattern(name, v))
+        .collect::<Vec<_>>();
+
+    let variant_names = getter_variants.iter().map(|v| v.ident.clone()).collect::<Vec<_>>();
+    let variant_types = getter_variants.iter().map(|v| v.data.fields()[0].ty.clone()).collect::<Vec<_>>();
+    let as_names = getter_variants.iter()
+        .map(|v| method_name("as", v))
+        .collect::<Vec<Ident>>();
+    let into_names = getter_variants.iter()
+        .map(|v| method_name("into", v))
+        .collect::<Vec<Ident>>();
+
+    let assoc_types = variant_names.clone();
+    let assoc_types2 = variant_names.clone();
+    let variant_types2 = variant_types.clone();
+    let as_names2 = as_names.clone();
+    let into_names2 = into_names.clone();
+    let is_names2 = is_names.clone();
+
+    let mut tokens = quote! {
+        /// Gives the methods of each variant through an `Option` or `Result` holding the enum, where `None`
+        /// and `Err` are treated as any other variant.
+        #[allow(dead_code)]
+        #vis trait #trait_name {
+            #(
+                /// The data moved out by the `into_*` method of this variant.
+                type #assoc_types;
+            )*
+
+            #(fn #is_names2(&self) -> bool;)*
+            #(fn #as_names2(&self) -> Option<&#variant_types2>;)*
+            #(fn #into_names2(self) -> Option<Self::#assoc_types2>;)*
+        }
+    };
+
+    // (implemented type, how to view it as `Option<&Enum>`, how to turn it into an `Option`,
+    // the reference pattern and binding for `into_*`, and the reference on its data)
+    let by_value = (quote! {}, quote! {}, quote! {});
+    let by_ref = (quote! { & }, quote! { ref }, quote! { &'a });
+    let by_mut = (quote! { &mut }, quote! { ref mut }, quote! { &'a mut });
+    let impls = vec![
+        (quote! { impl #trait_name for Option<#name> },
+         quote! { self.as_ref() }, quote! { self }, by_value.clone()),
+        (quote! { impl<'a> #trait_name for Option<&'a #name> },
+         quote! { *self }, quote! { self }, by_ref.clone()),
+        (quote! { impl<'a> #trait_name for Option<&'a mut #name> },
+         quote! { self.as_ref().map(|v| &**v) }, quote! { self }, by_mut.clone()),
+        (quote! { impl<__E> #trait_name for Result<#name, __E> },
+         quote! { self.as_ref().ok() }, quote! { self.ok() }, by_value),
+        (quote! { impl<'a, __E> #trait_name for Result<&'a #name, __E> },
+         quote! { self.as_ref().ok().map(|v| *v) }, quote! { self.ok() }, by_ref),
+        (quote! { impl<'a, __E> #trait_name for Result<&'a mut #name, __E> },
+         quote! { self.as_ref().ok().map(|v| &**v) }, quote! { self.ok() }, by_mut),
+    ];
+
+    for (header, view, into_view, (ref_pattern, binding, ref_ty)) in impls {
+        let is_methods = is_names.iter()
+            .zip(patterns.iter())
+            .map(|(is_name, pattern)| quote! {
+                fn #is_name(&self) -> bool {
+                    match #view {
+                        Some(&#pattern) => true,
+                        _ => false,
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let getters = variant_names.iter()
+            .zip(variant_types.iter())
+            .zip(as_names.iter().zip(into_names.iter()))
+            .map(|((variant_name, ty), (as_name, into_name))| quote! {
+                type #variant_name = #ref_ty #ty;
+
+                fn #as_name(&self) -> Option<&#ty> {
+                    match #view {
+                        Some(&#name::#variant_name(ref v)) => Some(v),
+                        _ => None,
+                    }
+                }
+
+                fn #into_name(self) -> Option<#ref_ty #ty> {
+                    match #into_view {
+                        Some(#ref_pattern #name::#variant_name(#binding v)) => Some(v),
+                        _ => None,
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        tokens.append(quote! {
+            #[allow(unreachable_patterns)]
+            #header {
+                #(#getters)*
+                #(#is_methods)*
+            }
+        });
+    }
+
+    tokens
+}
@@ -0,0 +1,86 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `{Enum}Partition`, which collects the data of each variant into its own `Vec` and counts
+/// unit variants.
+pub(crate) fn impl_enum_partition(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let partition: Ident = format!("{}Partition", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let extra_derives = enum_methods_list(&ast.attrs, "partition")
+        .map(|items| meta_list_words(items, "derive"))
+        .unwrap_or_default();
+
+    let field_names = variants.iter()
+        .map(|v| match v.data {
+            VariantData::Unit => format!("{}_count", to_snake_case(&v.ident)).into(),
+            _ => escape_keyword(to_snake_case(&v.ident)).into(),
+        })
+        .collect::<Vec<Ident>>();
+
+    // like the getters, a single member is collected as-is and several members as a tuple
+    let field_types = variants.iter()
+        .map(|v| {
+            let types = v.data.fields().iter().map(|f| &f.ty).collect::<Vec<_>>();
+            match types.len() {
+                0 => quote! { usize },
+                1 => quote! { Vec<#(#types)*> },
+                _ => quote! { Vec<(#(#types),*)> },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let arms = variants.iter()
+        .zip(field_names.iter())
+        .map(|(v, field_name)| {
+            let variant_name = &v.ident;
+            let fields = v.data.fields();
+            let args = match v.data {
+                VariantData::Struct(_) => fields.iter().map(|f| f.ident.clone().unwrap()).collect(),
+                _ => UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>(),
+            };
+            let args2 = args.clone();
+            let pattern = match v.data {
+                VariantData::Tuple(_) => quote! { #name::#variant_name(#(#args),*) },
+                VariantData::Struct(_) => quote! { #name::#variant_name { #(#args),* } },
+                VariantData::Unit => quote! { #name::#variant_name },
+            };
+            match fields.len() {
+                0 => quote! { #pattern => self.#field_name += 1 },
+                1 => quote! { #pattern => self.#field_name.push(#(#args2)*) },
+                _ => quote! { #pattern => self.#field_name.push((#(#args2),*)) },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[derive(Default, #(#extra_derives),*)]
+        #vis struct #partition {
+            #(pub #field_names: #field_types),*
+        }
+
+        impl ::std::iter::Extend<#name> for #partition {
+            fn extend<I: IntoIterator<Item = #name>>(&mut self, iter: I) {
+                for value in iter {
+                    match value {
+                        #(#arms),*
+                    }
+                }
+            }
+        }
+
+        impl ::std::iter::FromIterator<#name> for #partition {
+            fn from_iter<I: IntoIterator<Item = #name>>(iter: I) -> Self {
+                let mut partition = #partition::default();
+                partition.extend(iter);
+                partition
+            }
+        }
+    }
+}
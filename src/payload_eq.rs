@@ -0,0 +1,75 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `*_eq` methods comparing the data of every single-member tuple variant, along with
+/// `PartialEq<T> for Enum` for every unique type when the enum is marked with
+/// `#[enum_methods(payload_eq)]`.
+pub(crate) fn impl_enum_payload_eq(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let compared = variants.iter()
+        .filter(|v| if let VariantData::Tuple(ref fields) = v.data { fields.len() == 1 } else { false })
+        .collect::<Vec<_>>();
+
+    let eq_names = compared.iter()
+        .map(|v| format!("{}_eq", to_snake_case(&v.ident)).into())
+        .collect::<Vec<Ident>>();
+
+    let methods = compared.iter()
+        .zip(eq_names.iter())
+        .map(|(v, eq_name)| {
+            let variant_name = &v.ident;
+            let ty = &v.data.fields()[0].ty;
+            quote! {
+                /// Compares the data of this variant with the given value, giving `false` if the enum is a
+                /// different variant.
+                pub fn #eq_name<__U: ?Sized>(&self, other: &__U) -> bool where #ty: PartialEq<__U> {
+                    match *self {
+                        #name::#variant_name(ref v) => v == other,
+                        _ => false,
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut tokens = quote! {
+        #[allow(dead_code, unreachable_patterns)]
+        impl #name {
+            #(#methods)*
+        }
+    };
+
+    if !has_enum_methods_word(&ast.attrs, "payload_eq") {
+        return tokens;
+    }
+
+    // types are compared by their tokens, like EnumConversions does
+    let type_strs = compared.iter()
+        .map(|v| {
+            let ty = &v.data.fields()[0].ty;
+            quote! { #ty }.to_string()
+        })
+        .collect::<Vec<String>>();
+
+    for ((v, eq_name), ty_str) in compared.iter().zip(eq_names.iter()).zip(type_strs.iter()) {
+        if type_strs.iter().filter(|t| *t == ty_str).count() != 1 {
+            continue;
+        }
+        let ty = &v.data.fields()[0].ty;
+        tokens.append(quote! {
+            impl PartialEq<#ty> for #name {
+                fn eq(&self, other: &#ty) -> bool {
+                    self.#eq_name(other)
+                }
+            }
+        });
+    }
+
+    tokens
+}
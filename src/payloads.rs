@@ -0,0 +1,140 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gets the `payload` or `payload(...)` item out of a list of attributes, if it exists.
+fn payload_item(attrs: &[Attribute]) -> Option<&MetaItem> {
+    enum_methods_items(attrs)
+        .into_iter()
+        .find(|meta| meta.name() == "payload")
+}
+
+/// Gives standalone structs holding the data of variants which are marked with
+/// `#[enum_methods(payload)]`, or of every variant when the enum itself is marked.
+pub(crate) fn impl_enum_payloads(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let enum_payload = payload_item(&ast.attrs);
+
+    let payloads = variants.iter()
+        .filter(|v| v.data != VariantData::Unit)
+        .filter_map(|v| payload_item(&v.attrs).or(enum_payload).map(|item| (v, item)))
+        .map(|(v, item)| {
+            let variant_name = &v.ident;
+            let snake_name = to_snake_case(&v.ident);
+            let payload: Ident = format!("{}{}", name, variant_name).into();
+            let payload_ref: Ident = format!("{}{}Ref", name, variant_name).into();
+            let into_name_str = format!("into_{}_payload", snake_name);
+            let as_name_str = format!("as_{}_payload", snake_name);
+            let into_name: Ident = into_name_str.clone().into();
+            let as_name: Ident = as_name_str.clone().into();
+
+            let derives = match *item {
+                MetaItem::List(_, ref items) => meta_list_words(items, "derive"),
+                _ => Vec::new(),
+            };
+
+            let fields = v.data.fields();
+            let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+            let types2 = types.clone();
+
+            // each binding is used both as a pattern and as the expression rebuilding the same value,
+            // where tuple variants become tuple structs and struct variants keep their field names
+            let (definitions, enum_binding, enum_ref_binding, payload_binding, payload_ref_binding) =
+                if let VariantData::Tuple(_) = v.data {
+                    let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+                    let args2 = args.clone();
+                    let args3 = args.clone();
+                    let args4 = args.clone();
+                    (quote! {
+                        #[derive(#(#derives),*)]
+                        #vis struct #payload(#(pub #types),*);
+
+                        #[derive(Clone, Copy)]
+                        #vis struct #payload_ref<'a>(#(pub &'a #types2),*);
+                    },
+                     quote! { #name::#variant_name(#(#args),*) },
+                     quote! { #name::#variant_name(#(ref #args2),*) },
+                     quote! { #payload(#(#args3),*) },
+                     quote! { #payload_ref(#(#args4),*) })
+                }
+                else {
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    let field_names2 = field_names.clone();
+                    let field_names3 = field_names.clone();
+                    let field_names4 = field_names.clone();
+                    let field_names5 = field_names.clone();
+                    let field_names6 = field_names.clone();
+                    (quote! {
+                        #[derive(#(#derives),*)]
+                        #vis struct #payload {
+                            #(pub #field_names: #types),*
+                        }
+
+                        #[derive(Clone, Copy)]
+                        #vis struct #payload_ref<'a> {
+                            #(pub #field_names2: &'a #types2),*
+                        }
+                    },
+                     quote! { #name::#variant_name { #(#field_names3),* } },
+                     quote! { #name::#variant_name { #(ref #field_names4),* } },
+                     quote! { #payload { #(#field_names5),* } },
+                     quote! { #payload_ref { #(#field_names6),* } })
+                };
+
+            let mut tokens = definitions;
+            tokens.append(quote! {
+                impl From<#payload> for #name {
+                    fn from(payload: #payload) -> Self {
+                        let #payload_binding = payload;
+                        #enum_binding
+                    }
+                }
+
+                #[allow(unreachable_patterns)]
+                impl ::std::convert::TryFrom<#name> for #payload {
+                    type Error = #name;
+
+                    fn try_from(value: #name) -> Result<Self, Self::Error> {
+                        match value {
+                            #enum_binding => Ok(#payload_binding),
+                            value => Err(value),
+                        }
+                    }
+                }
+
+                #[allow(dead_code)]
+                impl #name {
+                    pub fn #into_name(self) -> #payload {
+                        if let #enum_binding = self {
+                            #payload_binding
+                        }
+                        else {
+                            panic!(concat!("called ", #into_name_str, "() on {:?}"), self);
+                        }
+                    }
+
+                    pub fn #as_name(&self) -> #payload_ref {
+                        if let #enum_ref_binding = *self {
+                            #payload_ref_binding
+                        }
+                        else {
+                            panic!(concat!("called ", #as_name_str, "() on {:?}"), self);
+                        }
+                    }
+                }
+            });
+
+            tokens
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#payloads)*
+    }
+}
@@ -0,0 +1,167 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives `set_*`, `replace_*` and `set_variant_*` methods for every tuple variant.
+pub(crate) fn impl_enum_setters(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    macro_rules! setter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(_) = v.data { true } else { false })
+        };
+    }
+
+    // `set_variant_foo` of `Foo` is also `set_variant_foo` of a variant named `VariantFoo`
+    let mut method_names: Vec<(String, &Ident)> = Vec::new();
+    for v in setter_filter!() {
+        let snake_name = to_snake_case(&v.ident);
+        for method_name in [format!("set_{}", snake_name), format!("replace_{}", snake_name),
+                                format!("set_variant_{}", snake_name)] {
+            if let Some(&(_, other)) = method_names.iter().find(|(m, _)| *m == method_name) {
+                panic!("the variants {} and {} of {} would both generate {}()", other, v.ident, name, method_name);
+            }
+            method_names.push((method_name, &v.ident));
+        }
+    }
+
+    let setters = setter_filter!()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let snake_name = to_snake_case(&v.ident);
+            let set_name: Ident = format!("set_{}", snake_name).into();
+            let replace_name: Ident = format!("replace_{}", snake_name).into();
+            let set_variant_name: Ident = format!("set_variant_{}", snake_name).into();
+
+            let fields = v.data.fields();
+            let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+            let (value_type, value, old_value) = if fields.len() == 1 {
+                let ty = &fields[0].ty;
+                let arg = &args[0];
+                (quote! { #ty }, quote! { value }, quote! { #arg })
+            } else {
+                let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                let indices = (0..fields.len()).map(Ident::from).collect::<Vec<_>>();
+                let args2 = args.clone();
+                (quote! { (#(#types),*) }, quote! { #(value.#indices),* }, quote! { (#(#args2),*) })
+            };
+
+            quote! {
+                /// Sets the data of this variant, giving the value back if the enum is a different variant.
+                pub fn #set_name(&mut self, value: #value_type) -> Result<(), #value_type> {
+                    if let #name::#variant_name(..) = *self {
+                        *self = #name::#variant_name(#value);
+                        Ok(())
+                    }
+                    else {
+                        Err(value)
+                    }
+                }
+
+                /// Switches the enum to this variant, returning the old data if it was already this variant.
+                pub fn #replace_name(&mut self, value: #value_type) -> Option<#value_type> {
+                    match ::std::mem::replace(self, #name::#variant_name(#value)) {
+                        #name::#variant_name(#(#args),*) => Some(#old_value),
+                        _ => None,
+                    }
+                }
+
+                /// Switches the enum to this variant, regardless of the current variant.
+                pub fn #set_variant_name(&mut self, value: #value_type) {
+                    *self = #name::#variant_name(#value);
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code, unreachable_patterns)]
+        impl #name {
+            #(#setters)*
+        }
+    }
+}
+
+/// Gives `take_*` methods for every tuple variant along with an `impl Default`, when a unit variant
+/// is marked with `#[enum_methods(default)]`.
+pub(crate) fn impl_enum_take(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let defaults = variants.iter()
+        .filter(|v| has_enum_methods_word(&v.attrs, "default"))
+        .collect::<Vec<_>>();
+
+    let default_variant = match defaults.len() {
+        0 => return quote! {},
+        1 => &defaults[0].ident,
+        _ => panic!("only one variant of {} may be marked with #[enum_methods(default)]", name),
+    };
+
+    if defaults[0].data != VariantData::Unit {
+        panic!("#[enum_methods(default)] may only be used on a unit variant, but {}::{} has data",
+               name, default_variant);
+    }
+
+    macro_rules! setter_filter {
+        () => {
+            variants.iter()
+                .filter(|v| if let VariantData::Tuple(_) = v.data { true } else { false })
+        };
+    }
+
+    let takers = setter_filter!()
+        .map(|v| {
+            let variant_name = &v.ident;
+            let take_name: Ident = format!("take_{}", to_snake_case(&v.ident)).into();
+
+            let fields = v.data.fields();
+            let args = UniqueIdentifierIterator::new().take(fields.len()).collect::<Vec<_>>();
+            let (value_type, value) = if fields.len() == 1 {
+                let ty = &fields[0].ty;
+                let arg = &args[0];
+                (quote! { #ty }, quote! { #arg })
+            } else {
+                let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                let args2 = args.clone();
+                (quote! { (#(#types),*) }, quote! { (#(#args2),*) })
+            };
+
+            quote! {
+                /// Takes the data out of this variant, leaving the default variant in its place.
+                pub fn #take_name(&mut self) -> Option<#value_type> {
+                    if let #name::#variant_name(..) = *self {
+                        match ::std::mem::replace(self, #name::#default_variant) {
+                            #name::#variant_name(#(#args),*) => Some(#value),
+                            _ => unreachable!(),
+                        }
+                    }
+                    else {
+                        None
+                    }
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[allow(dead_code)]
+        impl #name {
+            #(#takers)*
+        }
+
+        impl Default for #name {
+            fn default() -> Self {
+                #name::#default_variant
+            }
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use std::str;
-use syn::Ident;
+use syn::*;
+use quote;
 
 pub(crate) fn to_snake_case<S: AsRef<str>>(ident: &S) -> String {
     let mut snake_case = String::new();
@@ -15,6 +16,215 @@ pub(crate) fn to_snake_case<S: AsRef<str>>(ident: &S) -> String {
     snake_case
 }
 
+/// Appends an underscore to names which are keywords (including reserved ones), so they can be
+/// used as identifiers, e.g. `type` becomes `type_`.
+pub(crate) fn escape_keyword(name: String) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+        "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+        "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+        "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+    if KEYWORDS.contains(&name.as_str()) { name + "_" } else { name }
+}
+
+/// Collects every item nested inside of the `#[enum_methods(...)]` attributes in the given list.
+pub(crate) fn enum_methods_items(attrs: &[Attribute]) -> Vec<&MetaItem> {
+    attrs.iter()
+        .filter_map(|attr| match attr.value {
+            MetaItem::List(ref name, ref items) if name == "enum_methods" => Some(items),
+            _ => None,
+        })
+        .flat_map(|items| items.iter())
+        .map(|item| match *item {
+            NestedMetaItem::MetaItem(ref meta) => meta,
+            NestedMetaItem::Literal(_) => panic!("unexpected literal in #[enum_methods(...)] attribute"),
+        })
+        .collect()
+}
+
+/// Gets the nested items of every `#[enum_methods(name(...))]` list.
+pub(crate) fn enum_methods_lists<'a>(attrs: &'a [Attribute], name: &str) -> Vec<&'a [NestedMetaItem]> {
+    enum_methods_items(attrs)
+        .into_iter()
+        .filter_map(|meta| match *meta {
+            MetaItem::List(ref ident, ref items) if ident == name => Some(&items[..]),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Gets the nested items of the `#[enum_methods(name(...))]` list, if it exists.
+pub(crate) fn enum_methods_list<'a>(attrs: &'a [Attribute], name: &str) -> Option<&'a [NestedMetaItem]> {
+    enum_methods_lists(attrs, name).into_iter().next()
+}
+
+/// Checks whether the `#[enum_methods(name)]` word is present.
+pub(crate) fn has_enum_methods_word(attrs: &[Attribute], name: &str) -> bool {
+    enum_methods_items(attrs)
+        .into_iter()
+        .any(|meta| match *meta {
+            MetaItem::Word(ref ident) => ident == name,
+            _ => false,
+        })
+}
+
+/// Gets the string values of every `#[enum_methods(name = "value")]` item.
+pub(crate) fn enum_methods_str_values(attrs: &[Attribute], name: &str) -> Vec<String> {
+    enum_methods_items(attrs)
+        .into_iter()
+        .filter_map(|meta| match *meta {
+            MetaItem::NameValue(ref ident, Lit::Str(ref value, _)) if ident == name => Some(value.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Gets the string value of `name = "value"` out of a list of nested items.
+pub(crate) fn meta_str_value(items: &[NestedMetaItem], name: &str) -> Option<String> {
+    items.iter()
+        .filter_map(|item| match *item {
+            NestedMetaItem::MetaItem(MetaItem::NameValue(ref ident, Lit::Str(ref value, _)))
+                if ident == name => Some(value.clone()),
+            _ => None,
+        })
+        .next()
+}
+
+/// Gets the bare words out of the `name(...)` list within a list of nested items.
+pub(crate) fn meta_list_words(items: &[NestedMetaItem], name: &str) -> Vec<Ident> {
+    items.iter()
+        .filter_map(|item| match *item {
+            NestedMetaItem::MetaItem(MetaItem::List(ref ident, ref words)) if ident == name => Some(words),
+            _ => None,
+        })
+        .flat_map(|words| meta_words(words, name))
+        .collect()
+}
+
+/// Gets the bare words out of a list of nested items, which must only hold words.
+pub(crate) fn meta_words(items: &[NestedMetaItem], name: &str) -> Vec<Ident> {
+    items.iter()
+        .map(|word| match *word {
+            NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) => ident.clone(),
+            _ => panic!("expected only identifiers in {}(...)", name),
+        })
+        .collect()
+}
+
+/// Creates a pattern that matches the given variant, ignoring any data it holds.
+pub(crate) fn variant_wildcard_pattern(name: &Ident, variant: &Variant) -> quote::Tokens {
+    let variant_name = &variant.ident;
+    match variant.data {
+        VariantData::Tuple(_) => quote! { #name::#variant_name(..) },
+        VariantData::Struct(_) => quote! { #name::#variant_name { .. } },
+        VariantData::Unit => quote! { #name::#variant_name },
+    }
+}
+
+/// A variant of an inner enum listed with `#[enum_methods(flatten(...))]` on a variant holding that
+/// enum, e.g. `Int` in `Literal(Literal)` marked with `flatten(Int = "i64")`.
+pub(crate) struct FlattenedVariant<'a> {
+    pub(crate) outer: &'a Variant,
+    /// The path of the inner enum as it is written in patterns, e.g. `Lit::<u8>` for `Lit<u8>`.
+    pub(crate) inner_enum: quote::Tokens,
+    /// Whether the inner enum is held in a `Box`, which has to be dereferenced to match on it.
+    pub(crate) boxed: bool,
+    pub(crate) inner: Ident,
+    /// The type held by the inner variant, if one was given; only then are getters generated.
+    pub(crate) ty: Option<Ty>,
+}
+
+impl<'a> FlattenedVariant<'a> {
+    /// Gets the snake_case name shared by the composed methods, e.g. `literal_int`.
+    pub(crate) fn snake_name(&self) -> String {
+        format!("{}_{}", to_snake_case(&self.outer.ident), to_snake_case(&self.inner))
+    }
+}
+
+/// Collects every inner variant listed with `#[enum_methods(flatten(...))]` on the enum's variants.
+pub(crate) fn flattened_variants(ast: &DeriveInput) -> Vec<FlattenedVariant<'_>> {
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let mut flattened = Vec::new();
+    for v in variants {
+        if has_enum_methods_word(&v.attrs, "flatten") {
+            panic!("#[enum_methods(flatten)] on {}::{} must list the variants of the inner enum, \
+                    e.g. flatten(Int = \"i64\", Nil)", ast.ident, v.ident);
+        }
+        let lists = enum_methods_lists(&v.attrs, "flatten");
+        if lists.is_empty() {
+            continue;
+        }
+        let inner_ty = match v.data {
+            VariantData::Tuple(ref fields) if fields.len() == 1 => &fields[0].ty,
+            _ => panic!("#[enum_methods(flatten(...))] may only be used on a tuple variant with exactly 1 member, \
+                         but {}::{} is not", ast.ident, v.ident),
+        };
+        let (inner_path, boxed) = match boxed_type(inner_ty) {
+            Some(ty) => (ty, true),
+            None => (inner_ty, false),
+        };
+        let inner_enum = match *inner_path {
+            Ty::Path(None, ref path) => pattern_path(path),
+            _ => panic!("#[enum_methods(flatten(...))] on {}::{} needs the variant to hold an enum by name, \
+                         optionally in a Box", ast.ident, v.ident),
+        };
+        for item in lists.into_iter().flat_map(|items| items.iter()) {
+            let (inner, ty) = match *item {
+                NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) => (ident.clone(), None),
+                NestedMetaItem::MetaItem(MetaItem::NameValue(ref ident, Lit::Str(ref ty, _))) => {
+                    let ty = parse_type(ty)
+                        .unwrap_or_else(|e| panic!("invalid type for {} in flatten(...): {}", ident, e));
+                    (ident.clone(), Some(ty))
+                }
+                _ => panic!("expected `Variant` or `Variant = \"Type\"` in flatten(...)"),
+            };
+            flattened.push(FlattenedVariant { outer: v, inner_enum: inner_enum.clone(), boxed, inner, ty });
+        }
+    }
+    flattened
+}
+
+/// Gets the type held by a `Box<T>` type.
+fn boxed_type(ty: &Ty) -> Option<&Ty> {
+    let path = match *ty {
+        Ty::Path(None, ref path) => path,
+        _ => return None,
+    };
+    let last = path.segments.last().unwrap();
+    match last.parameters {
+        PathParameters::AngleBracketed(ref data) if last.ident == "Box" && data.types.len() == 1 => Some(&data.types[0]),
+        _ => None,
+    }
+}
+
+/// Writes a type path so it can be used in a pattern, which needs a turbofish before any generic
+/// arguments, e.g. `Lit::<u8>` for `Lit<u8>`.
+fn pattern_path(path: &Path) -> quote::Tokens {
+    let mut tokens = quote::Tokens::new();
+    if path.global {
+        tokens.append("::");
+    }
+    for (i, segment) in path.segments.iter().enumerate() {
+        if i > 0 {
+            tokens.append("::");
+        }
+        let ident = &segment.ident;
+        let parameters = &segment.parameters;
+        if parameters.is_empty() {
+            tokens.append(quote! { #ident });
+        }
+        else {
+            tokens.append(quote! { #ident::#parameters });
+        }
+    }
+    tokens
+}
+
 pub(crate) struct UniqueIdentifierIterator {
     buffer: Vec<u8>,
 }
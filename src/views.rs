@@ -0,0 +1,132 @@
+use syn::*;
+use quote;
+
+/// Gives the `{Enum}Ref<'a>` and `{Enum}Mut<'a>` view enums, which mirror each variant with
+/// borrowed data.
+pub(crate) fn impl_enum_views(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let view: Ident = format!("{}Ref", name).into();
+    let view_mut: Ident = format!("{}Mut", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    // the views' lifetime must be used by at least one variant
+    if variants.iter().all(|v| v.data.fields().is_empty()) {
+        panic!("EnumViews requires at least one variant of {} to hold data", name);
+    }
+
+    // each variant is given as its declaration in the views, the pattern binding its data in the
+    // enum and a view, and the expression rebuilding it from those bindings
+    let view_variants = variants.iter()
+        .map(|v| {
+            let variant_name = &v.ident;
+            match v.data {
+                VariantData::Tuple(ref fields) => {
+                    let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                    let types2 = types.clone();
+                    let args = (0..fields.len()).map(|i| Ident::from(format!("v{}", i))).collect::<Vec<_>>();
+                    let args2 = args.clone();
+                    let args3 = args.clone();
+                    let args4 = args.clone();
+                    let args5 = args.clone();
+                    (quote! { #variant_name(#(&'a #types),*) },
+                     quote! { #variant_name(#(&'a mut #types2),*) },
+                     quote! { #variant_name(#(ref #args),*) },
+                     quote! { #variant_name(#(ref mut #args2),*) },
+                     quote! { #variant_name(#(#args3),*) },
+                     quote! { #variant_name(#(Clone::clone(#args4)),*) },
+                     quote! { #variant_name(#(#args5),*) })
+                }
+                VariantData::Struct(ref fields) => {
+                    let types = fields.iter().map(|f| &f.ty).collect::<Vec<_>>();
+                    let types2 = types.clone();
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    let field_names2 = field_names.clone();
+                    let field_names3 = field_names.clone();
+                    let field_names4 = field_names.clone();
+                    let field_names5 = field_names.clone();
+                    let field_names6 = field_names.clone();
+                    let field_names7 = field_names.clone();
+                    let field_names8 = field_names.clone();
+                    (quote! { #variant_name { #(#field_names: &'a #types),* } },
+                     quote! { #variant_name { #(#field_names2: &'a mut #types2),* } },
+                     quote! { #variant_name { #(ref #field_names3),* } },
+                     quote! { #variant_name { #(ref mut #field_names4),* } },
+                     quote! { #variant_name { #(#field_names5),* } },
+                     quote! { #variant_name { #(#field_names6: Clone::clone(#field_names7)),* } },
+                     quote! { #variant_name { #(#field_names8),* } })
+                }
+                VariantData::Unit => {
+                    (quote! { #variant_name },
+                     quote! { #variant_name },
+                     quote! { #variant_name },
+                     quote! { #variant_name },
+                     quote! { #variant_name },
+                     quote! { #variant_name },
+                     quote! { #variant_name })
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let ref_declarations = view_variants.iter().map(|t| t.0.clone()).collect::<Vec<_>>();
+    let mut_declarations = view_variants.iter().map(|t| t.1.clone()).collect::<Vec<_>>();
+    let ref_patterns = view_variants.iter().map(|t| t.2.clone()).collect::<Vec<_>>();
+    let mut_patterns = view_variants.iter().map(|t| t.3.clone()).collect::<Vec<_>>();
+    let view_patterns = view_variants.iter().map(|t| t.4.clone()).collect::<Vec<_>>();
+    let cloned_values = view_variants.iter().map(|t| t.5.clone()).collect::<Vec<_>>();
+    let view_values = view_variants.iter().map(|t| t.6.clone()).collect::<Vec<_>>();
+    let view_values2 = view_values.clone();
+
+    let names = vec!(name.clone(); view_variants.len());
+    let names2 = names.clone();
+    let names3 = names.clone();
+    let views = vec!(view.clone(); view_variants.len());
+    let views2 = views.clone();
+    let views_mut = vec!(view_mut.clone(); view_variants.len());
+
+    // `to_owned` can only be called when every member is `Clone`, and the higher-ranked bounds
+    // defer that check until it is called
+    let member_types = variants.iter()
+        .flat_map(|v| v.data.fields().iter().map(|f| &f.ty))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #[derive(Clone, Copy)]
+        #vis enum #view<'a> {
+            #(#ref_declarations),*
+        }
+
+        #vis enum #view_mut<'a> {
+            #(#mut_declarations),*
+        }
+
+        #[allow(dead_code)]
+        impl #name {
+            pub fn as_view(&self) -> #view {
+                match *self {
+                    #(#names::#ref_patterns => #views::#view_values),*
+                }
+            }
+
+            pub fn as_view_mut(&mut self) -> #view_mut {
+                match *self {
+                    #(#names2::#mut_patterns => #views_mut::#view_values2),*
+                }
+            }
+        }
+
+        #[allow(dead_code)]
+        impl<'a> #view<'a> {
+            /// Clones the borrowed data into a new enum.
+            pub fn to_owned(&self) -> #name where #(for<'c> #member_types: Clone),* {
+                match *self {
+                    #(#views2::#view_patterns => #names3::#cloned_values),*
+                }
+            }
+        }
+    }
+}
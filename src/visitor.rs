@@ -0,0 +1,112 @@
+use syn::*;
+use quote;
+use util::*;
+
+/// Gives the `{Enum}Visitor` and `{Enum}VisitorMut` traits, with one method per variant, along
+/// with the `accept` and `accept_mut` methods calling them.
+pub(crate) fn impl_enum_visitor(ast: &DeriveInput) -> quote::Tokens {
+    let name = &ast.ident;
+    let vis = &ast.vis;
+    let visitor: Ident = format!("{}Visitor", name).into();
+    let visitor_mut: Ident = format!("{}VisitorMut", name).into();
+
+    let variants =
+        if let Body::Enum(ref e) = ast.body { e }
+        else { unreachable!() };
+
+    let function_names = variants.iter()
+        .map(|v| format!("visit_{}", to_snake_case(&v.ident)).into())
+        .collect::<Vec<Ident>>();
+
+    // tuple members are passed as `a`, `b`, ..., while struct fields keep their names
+    let arg_names = variants.iter()
+        .map(|v| match v.data {
+            VariantData::Struct(ref fields) => fields.iter().map(|f| f.ident.clone().unwrap()).collect(),
+            _ => UniqueIdentifierIterator::new().take(v.data.fields().len()).collect::<Vec<_>>(),
+        })
+        .collect::<Vec<_>>();
+
+    let arg_types = variants.iter()
+        .map(|v| v.data.fields().iter().map(|f| f.ty.clone()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // the members are bound to generated names in `accept`, so a field named `visitor` can't shadow
+    // the visitor itself
+    let bindings = variants.iter()
+        .map(|v| UniqueIdentifierIterator::new().take(v.data.fields().len()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let patterns = variants.iter()
+        .zip(bindings.iter())
+        .map(|(v, bindings)| {
+            let variant_name = &v.ident;
+            let bindings = bindings.clone();
+            match v.data {
+                VariantData::Tuple(_) => quote! { #name::#variant_name(#(ref #bindings),*) },
+                VariantData::Struct(ref fields) => {
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    quote! { #name::#variant_name { #(#field_names: ref #bindings),* } }
+                }
+                VariantData::Unit => quote! { #name::#variant_name },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let patterns_mut = variants.iter()
+        .zip(bindings.iter())
+        .map(|(v, bindings)| {
+            let variant_name = &v.ident;
+            let bindings = bindings.clone();
+            match v.data {
+                VariantData::Tuple(_) => quote! { #name::#variant_name(#(ref mut #bindings),*) },
+                VariantData::Struct(ref fields) => {
+                    let field_names = fields.iter().map(|f| f.ident.clone().unwrap()).collect::<Vec<_>>();
+                    quote! { #name::#variant_name { #(#field_names: ref mut #bindings),* } }
+                }
+                VariantData::Unit => quote! { #name::#variant_name },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let function_names2 = function_names.clone();
+    let function_names3 = function_names.clone();
+    let function_names4 = function_names.clone();
+    let arg_names2 = arg_names.clone();
+    let bindings2 = bindings.clone();
+    let arg_types2 = arg_types.clone();
+
+    let mut tokens = quote! {
+        #vis trait #visitor {
+            type Output;
+
+            #(fn #function_names(&mut self, #(#arg_names: &#arg_types),*) -> Self::Output;)*
+        }
+
+        #vis trait #visitor_mut {
+            type Output;
+
+            #(fn #function_names2(&mut self, #(#arg_names2: &mut #arg_types2),*) -> Self::Output;)*
+        }
+    };
+
+    tokens.append(quote! {
+        #[allow(dead_code)]
+        impl #name {
+            /// Calls the visitor method matching this variant.
+            pub fn accept<__V: #visitor>(&self, visitor: &mut __V) -> __V::Output {
+                match *self {
+                    #(#patterns => visitor.#function_names3(#(#bindings),*)),*
+                }
+            }
+
+            /// Calls the mutable visitor method matching this variant.
+            pub fn accept_mut<__V: #visitor_mut>(&mut self, visitor: &mut __V) -> __V::Output {
+                match *self {
+                    #(#patterns_mut => visitor.#function_names4(#(#bindings2),*)),*
+                }
+            }
+        }
+    });
+
+    tokens
+}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 #[macro_use]
 extern crate enum_methods;
 
@@ -71,3 +73,41 @@ fn test_getter_structs() {
     );
     assert_eq!(*third.as_some_struct(), 42);
 }
+
+#[test]
+fn test_positional_as_getters() {
+    #[derive(EnumAsGetters, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Tup(i32, String, Vec<bool>),
+        #[enum_methods(fields(id, name, flags))]
+        Named(i32, String, Vec<bool>),
+    }
+
+    let mut tup = MyEnum::Tup(42, String::from("tuple"), vec![true]);
+    assert_eq!(*tup.as_tup_0(), 42);
+    assert_eq!(tup.as_tup_1(), "tuple");
+    assert_eq!(tup.as_tup_2(), &vec![true]);
+    tup.as_tup_1_mut().push('!');
+    tup.as_tup_2_mut().push(false);
+    assert_eq!(tup.as_tup(), (&42, &String::from("tuple!"), &vec![true, false]));
+
+    let mut named = MyEnum::Named(7, String::from("named"), vec![]);
+    assert_eq!(*named.as_named_id(), 7);
+    assert_eq!(named.as_named_name(), "named");
+    *named.as_named_id_mut() += 1;
+    assert_eq!(*named.as_named_id(), 8);
+    assert!(named.as_named_flags().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "called as_tup_1() on Foo(1)")]
+fn test_positional_as_getter_wrong_variant() {
+    #[derive(EnumAsGetters, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Tup(i32, String),
+    }
+
+    MyEnum::Foo(1).as_tup_1();
+}
@@ -0,0 +1,57 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumBuilders, Debug, PartialEq)]
+enum Request {
+    #[enum_methods(builder)]
+    Get {
+        url: String,
+        timeout: Option<u32>,
+        #[enum_methods(default)]
+        headers: Vec<(String, String)>,
+    },
+    #[enum_methods(builder)]
+    Post { url: String, body: Vec<u8> },
+    Head { url: String },
+    Cancel(u64),
+}
+
+#[test]
+fn test_builders() {
+    let get = Request::get_builder().url("http://example.com").build();
+    assert_eq!(
+        get,
+        Request::Get {
+            url: "http://example.com".to_string(),
+            timeout: None,
+            headers: vec![],
+        }
+    );
+
+    let get = Request::get_builder()
+        .timeout(30)
+        .headers(vec![("Accept".to_string(), "*/*".to_string())])
+        .url("http://example.com")
+        .build();
+    assert_eq!(
+        get,
+        Request::Get {
+            url: "http://example.com".to_string(),
+            timeout: Some(30),
+            headers: vec![("Accept".to_string(), "*/*".to_string())],
+        }
+    );
+
+    let post = Request::post_builder().url("/").body(vec![1, 2, 3]).build();
+    assert_eq!(post, Request::Post { url: "/".to_string(), body: vec![1, 2, 3] });
+}
+
+#[test]
+fn test_builder_sets_fields_in_any_order() {
+    // a missing required field leaves the builder without `build()`, so this is checked at compile
+    // time; setting a field twice keeps the last value
+    let post = Request::post_builder().body(vec![1]).url("/a").url("/b").build();
+    assert_eq!(post, Request::Post { url: "/b".to_string(), body: vec![1] });
+}
@@ -0,0 +1,45 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+use std::collections::{BTreeSet, HashSet};
+
+#[derive(EnumByVariant, Debug)]
+enum Sample {
+    Float(f64),
+    Pair(f64, f64),
+    Point { x: f64, y: f64 },
+    Empty,
+}
+
+#[test]
+fn test_same_variant() {
+    assert!(Sample::Float(1.0).same_variant(&Sample::Float(f64::NAN)));
+    assert!(Sample::Point { x: 0.0, y: 1.0 }.same_variant(&Sample::Point { x: 2.0, y: 3.0 }));
+    assert!(Sample::Empty.same_variant(&Sample::Empty));
+    assert!(!Sample::Float(1.0).same_variant(&Sample::Pair(1.0, 1.0)));
+}
+
+#[test]
+fn test_by_variant() {
+    let values = [
+        Sample::Empty,
+        Sample::Float(1.0),
+        Sample::Pair(1.0, 2.0),
+        Sample::Float(2.0),
+        Sample::Empty,
+    ];
+
+    let hashed = values.iter().map(Sample::by_variant).collect::<HashSet<_>>();
+    assert_eq!(hashed.len(), 3);
+    assert!(hashed.contains(&SampleByVariant(&Sample::Pair(0.0, 0.0))));
+    assert!(!hashed.contains(&Sample::Point { x: 0.0, y: 0.0 }.by_variant()));
+
+    // ordered by declaration, not by data
+    let ordered = values.iter().map(SampleByVariant).collect::<BTreeSet<_>>();
+    let order = [Sample::Float(0.0), Sample::Pair(0.0, 0.0), Sample::Empty];
+    assert_eq!(ordered.len(), order.len());
+    assert!(ordered.iter().zip(order.iter()).all(|(a, b)| a.0.same_variant(b)));
+    assert!(Sample::Float(9.0).by_variant() < Sample::Empty.by_variant());
+}
@@ -0,0 +1,105 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumCombinators, Debug)]
+enum MyEnum {
+    Foo(i64),
+    Bar(bool),
+    Baz(String),
+    Tup(i32, String, Vec<bool>),
+    SomeStruct { foo: i32 },
+    Unit,
+}
+
+#[test]
+fn test_map_and_is_and() {
+    let foo = MyEnum::Foo(42);
+    let tup = MyEnum::Tup(1, "one".to_string(), vec![true]);
+
+    assert!(foo.is_foo_and(|v| *v > 40));
+    assert!(!foo.is_foo_and(|v| *v < 40));
+    assert!(!foo.is_bar_and(|v| *v));
+    assert!(tup.is_tup_and(|(a, b, c)| *a == 1 && b == "one" && c[0]));
+
+    assert_eq!(MyEnum::Foo(42).map_foo(|v| v * 2), Some(84));
+    assert_eq!(MyEnum::Unit.map_foo(|v| v * 2), None);
+    assert_eq!(tup.map_tup(|(a, b, _)| format!("{}{}", a, b)), Some("1one".to_string()));
+    assert_eq!(MyEnum::SomeStruct { foo: 1 }.map_baz(|s| s.len()), None);
+}
+
+#[test]
+fn test_or() {
+    assert_eq!(MyEnum::Foo(42).foo_or(0), 42);
+    assert_eq!(MyEnum::Bar(true).foo_or(0), 0);
+    assert_eq!(MyEnum::Bar(true).foo_or_else(|| -1), -1);
+    assert_eq!(MyEnum::Baz("hi".to_string()).baz_or_else(String::new), "hi");
+    assert_eq!(MyEnum::Unit.baz_or_default(), "");
+    assert_eq!(MyEnum::Unit.tup_or_default(), (0, String::new(), vec![]));
+    assert_eq!(
+        MyEnum::Unit.tup_or((2, "two".to_string(), vec![false])),
+        (2, "two".to_string(), vec![false])
+    );
+}
+
+#[test]
+fn test_or_default_without_default_payload() {
+    #[derive(Debug, PartialEq)]
+    struct NoDefault;
+
+    #[derive(EnumCombinators, Debug)]
+    enum Holder {
+        Nothing(NoDefault),
+        Number(u8),
+    }
+
+    // or_default is only usable for payloads implementing Default
+    assert_eq!(Holder::Nothing(NoDefault).number_or_default(), 0);
+    assert_eq!(Holder::Number(1).nothing_or(NoDefault), NoDefault);
+}
+
+#[test]
+fn test_inspect() {
+    let mut seen = Vec::new();
+    MyEnum::Foo(1)
+        .inspect_foo(|v| seen.push(*v))
+        .inspect_bar(|_| panic!("not a bar"));
+    MyEnum::Tup(2, String::new(), vec![]).inspect_tup(|(a, _, _)| seen.push(*a as i64));
+    assert_eq!(seen, vec![1, 2]);
+}
+
+#[test]
+fn test_combinators_many_members() {
+    #[derive(EnumCombinators, Debug)]
+    enum Wide {
+        Six(u8, u8, u8, u8, u8, u8),
+        Empty,
+    }
+
+    let six = Wide::Six(1, 2, 3, 4, 5, 6);
+    assert!(six.is_six_and(|(_, _, _, _, _, f)| *f == 6));
+    let mut last = 0;
+    six.inspect_six(|(_, _, _, _, _, f)| last = *f);
+    assert_eq!(last, 6);
+    assert_eq!(six.map_six(|(a, _, _, _, _, f)| a + f), Some(7));
+    assert_eq!(Wide::Empty.map_six(|(a, ..)| a), None);
+}
+
+#[test]
+fn test_combinators_payloads_named_like_generics() {
+    #[derive(Debug, PartialEq)]
+    struct F(u8);
+    #[derive(Debug, PartialEq)]
+    struct U(u8);
+
+    #[derive(EnumCombinators, Debug)]
+    enum Holder {
+        Foo(F),
+        Bar(U),
+    }
+
+    assert_eq!(Holder::Foo(F(1)).map_foo(|F(v)| v), Some(1));
+    assert!(Holder::Bar(U(2)).is_bar_and(|u| *u == U(2)));
+    assert_eq!(Holder::Bar(U(2)).foo_or_else(|| F(3)), F(3));
+}
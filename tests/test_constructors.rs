@@ -0,0 +1,31 @@
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_constructors() {
+    #[derive(EnumConstructors, Debug, PartialEq)]
+    enum MyEnum {
+        Foo(i64),
+        Baz(String),
+        Tup(i32, String, Vec<bool>),
+        SomeStruct { foo: i32, bar: String },
+        Unit,
+    }
+
+    assert_eq!(MyEnum::new_foo(42), MyEnum::Foo(42));
+    assert_eq!(MyEnum::new_foo(42i32), MyEnum::Foo(42));
+    assert_eq!(MyEnum::new_baz("the long way home"), MyEnum::Baz("the long way home".to_string()));
+    assert_eq!(
+        MyEnum::new_tup(1i8, "one", vec![true]),
+        MyEnum::Tup(1, "one".to_string(), vec![true])
+    );
+    assert_eq!(
+        MyEnum::new_tup_from((1, "one".to_string(), vec![true])),
+        MyEnum::Tup(1, "one".to_string(), vec![true])
+    );
+    assert_eq!(
+        MyEnum::new_some_struct(7u8, "seven"),
+        MyEnum::SomeStruct { foo: 7, bar: "seven".to_string() }
+    );
+    assert_ne!(MyEnum::Unit, MyEnum::new_foo(0));
+}
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+use std::convert::TryFrom;
+use std::io;
+
+#[test]
+fn test_conversions() {
+    #[derive(EnumConversions, Debug)]
+    enum MyError {
+        Io(io::Error),
+        Parse(std::num::ParseIntError),
+        Message(String),
+        Pair(i32, i32),
+        Unknown,
+    }
+
+    fn parse(s: &str) -> Result<i32, MyError> {
+        Ok(s.parse::<i32>()?)
+    }
+
+    assert!(parse("42").is_ok());
+    match parse("forty-two") {
+        Err(MyError::Parse(_)) => {}
+        other => panic!("expected a parse error, got {:?}", other),
+    }
+
+    let err = MyError::from(io::Error::other("oh no"));
+    assert_eq!(io::Error::try_from(err).unwrap().to_string(), "oh no");
+
+    let message = MyError::from("everybody wants to rule the world".to_string());
+    let message = match io::Error::try_from(message) {
+        Err(e) => e,
+        Ok(_) => panic!("expected a message"),
+    };
+    assert_eq!(String::try_from(message).unwrap(), "everybody wants to rule the world");
+    assert!(String::try_from(MyError::Unknown).is_err());
+    assert!(String::try_from(MyError::Pair(1, 2)).is_err());
+}
+
+#[test]
+fn test_ambiguous_conversions() {
+    #[derive(EnumConversions, Debug, PartialEq)]
+    enum Message {
+        #[enum_methods(from)]
+        Text(String),
+        Error(String),
+        Code(u16),
+    }
+
+    assert_eq!(Message::from("hello".to_string()), Message::Text("hello".to_string()));
+    assert_eq!(Message::from(404), Message::Code(404));
+    assert_eq!(u16::try_from(Message::Code(200)), Ok(200));
+    assert_eq!(
+        String::try_from(Message::Error("unreachable".to_string())),
+        Err(Message::Error("unreachable".to_string()))
+    );
+}
@@ -0,0 +1,49 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_field_accessors() {
+    #[derive(EnumFieldAccessors, Debug, PartialEq)]
+    enum MyEnum {
+        Foo(i64),
+        SomeStruct { foo: i32, bar: String },
+        OtherStruct { foo: bool },
+        Unit,
+    }
+
+    let mut some_struct = MyEnum::SomeStruct { foo: 42, bar: "bar".to_string() };
+    let mut other = MyEnum::OtherStruct { foo: true };
+
+    assert_eq!(some_struct.some_struct_foo(), Some(&42));
+    assert_eq!(some_struct.some_struct_bar().map(String::as_str), Some("bar"));
+    assert_eq!(other.some_struct_foo(), None);
+    assert_eq!(other.other_struct_foo(), Some(&true));
+    assert_eq!(MyEnum::Unit.other_struct_foo(), None);
+
+    *some_struct.some_struct_foo_mut().unwrap() += 1;
+    some_struct.some_struct_bar_mut().unwrap().push('!');
+    assert_eq!(some_struct, MyEnum::SomeStruct { foo: 43, bar: "bar!".to_string() });
+    assert!(other.some_struct_foo_mut().is_none());
+
+    assert_eq!(other.set_other_struct_foo(false), Ok(()));
+    assert_eq!(other, MyEnum::OtherStruct { foo: false });
+    assert_eq!(other.set_some_struct_foo(1), Err(1));
+    assert_eq!(some_struct.set_some_struct_bar("baz".to_string()), Ok(()));
+    assert_eq!(some_struct.some_struct_bar().unwrap(), "baz");
+}
+
+#[test]
+fn test_field_named_value() {
+    #[derive(EnumFieldAccessors, Debug, PartialEq)]
+    enum Expr {
+        Number { value: i64 },
+        Nil,
+    }
+
+    let mut number = Expr::Number { value: 1 };
+    assert_eq!(number.set_number_value(2), Ok(()));
+    assert_eq!(number, Expr::Number { value: 2 });
+    assert_eq!(Expr::Nil.set_number_value(3), Err(3));
+}
@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumIsA, Debug)]
+enum Literal {
+    Int(i64),
+    Str(String),
+    Nil,
+}
+
+#[derive(EnumIsA, EnumAsGetters, EnumIntoGetters, Debug)]
+enum Expr {
+    #[enum_methods(flatten(Int = "i64", Str = "String", Nil))]
+    Literal(Literal),
+    Neg(Box<Expr>),
+}
+
+#[test]
+fn test_flattened_is_a() {
+    let int = Expr::Literal(Literal::Int(1));
+    assert!(int.is_literal_int());
+    assert!(!int.is_literal_str());
+    assert!(!int.is_literal_nil());
+    assert!(Expr::Literal(Literal::Nil).is_literal_nil());
+
+    let neg = Expr::Neg(Box::new(Expr::Literal(Literal::Int(1))));
+    assert!(!neg.is_literal_int());
+    assert!(neg.as_neg().is_literal_int());
+}
+
+#[test]
+fn test_flattened_getters() {
+    let mut int = Expr::Literal(Literal::Int(1));
+    assert_eq!(*int.as_literal_int(), 1);
+    *int.as_literal_int_mut() += 1;
+    assert_eq!(int.into_literal_int(), 2);
+
+    let s = Expr::Literal(Literal::Str("x".to_string()));
+    assert_eq!(s.as_literal_str(), "x");
+    assert_eq!(s.into_literal_str(), "x");
+}
+
+#[test]
+#[should_panic(expected = "called as_literal_int() on Literal(Nil)")]
+fn test_flattened_getter_panics() {
+    Expr::Literal(Literal::Nil).as_literal_int();
+}
+
+#[test]
+#[should_panic(expected = "called into_literal_str() on Neg(")]
+fn test_flattened_into_getter_panics() {
+    Expr::Neg(Box::new(Expr::Literal(Literal::Nil))).into_literal_str();
+}
+
+#[test]
+fn test_flattened_boxed() {
+    #[derive(EnumIsA, EnumAsGetters, EnumIntoGetters, Debug)]
+    enum Boxed {
+        #[enum_methods(flatten(Int = "i64", Nil))]
+        Literal(Box<Literal>),
+        Other,
+    }
+
+    let mut int = Boxed::Literal(Box::new(Literal::Int(1)));
+    assert!(int.is_literal_int());
+    assert!(!int.is_literal_nil());
+    *int.as_literal_int_mut() += 1;
+    assert_eq!(*int.as_literal_int(), 2);
+    assert_eq!(int.into_literal_int(), 2);
+    assert!(Boxed::Literal(Box::new(Literal::Nil)).is_literal_nil());
+    assert!(!Boxed::Other.is_literal_int());
+}
+
+#[test]
+fn test_flattened_generic() {
+    #[derive(Debug)]
+    enum Lit<T> {
+        Value(T),
+        Empty,
+    }
+
+    #[derive(EnumAsGetters, EnumIntoGetters, Debug)]
+    enum Generic {
+        #[enum_methods(flatten(Value = "u8"))]
+        Lit(Lit<u8>),
+    }
+
+    assert_eq!(*Generic::Lit(Lit::Value(3)).as_lit_value(), 3);
+    assert_eq!(Generic::Lit(Lit::Value(4)).into_lit_value(), 4);
+}
+
+#[test]
+#[should_panic(expected = "called into_lit_value() on Lit(Empty)")]
+fn test_flattened_generic_panics() {
+    #[derive(Debug)]
+    enum Lit<T> {
+        Value(T),
+        Empty,
+    }
+
+    #[derive(EnumIntoGetters, Debug)]
+    enum Generic {
+        #[enum_methods(flatten(Value = "u8"))]
+        Lit(Lit<u8>),
+    }
+
+    Generic::Lit(Lit::Empty).into_lit_value();
+}
@@ -0,0 +1,75 @@
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumFold, Debug)]
+enum MyEnum {
+    Foo(i64),
+    Tup(i32, String),
+    SomeStruct { foo: i32 },
+    BiggerStruct { foo: i32, bar: bool },
+    Unit,
+}
+
+fn describe(value: MyEnum) -> String {
+    value.fold(
+        |v| format!("foo {}", v),
+        |(a, b)| format!("tup {} {}", a, b),
+        |v| format!("struct {}", v),
+        |(a, b)| format!("bigger {} {}", a, b),
+        || "unit".to_string(),
+    )
+}
+
+#[test]
+fn test_fold() {
+    assert_eq!(describe(MyEnum::Foo(42)), "foo 42");
+    assert_eq!(describe(MyEnum::Tup(1, "one".to_string())), "tup 1 one");
+    assert_eq!(describe(MyEnum::SomeStruct { foo: 2 }), "struct 2");
+    assert_eq!(describe(MyEnum::BiggerStruct { foo: 3, bar: true }), "bigger 3 true");
+    assert_eq!(describe(MyEnum::Unit), "unit");
+}
+
+#[test]
+fn test_fold_moves_data() {
+    let name = String::from("moved");
+    let owned = MyEnum::Tup(0, name).fold(|_| None, |(_, b)| Some(b), |_| None, |_| None, || None);
+    assert_eq!(owned, Some(String::from("moved")));
+}
+
+#[test]
+fn test_fold_keyword_variants() {
+    #[derive(EnumFold)]
+    enum Tok {
+        Fn,
+        Type(u8),
+    }
+
+    assert_eq!(Tok::Fn.fold(|| 0, |t| t), 0);
+    assert_eq!(Tok::Type(7).fold(|| 0, |t| t), 7);
+}
+
+#[test]
+fn test_fold_bindings_named_like_variants() {
+    #[derive(EnumFold)]
+    enum Node {
+        Value { value: i64 },
+        B(u8, u8),
+    }
+
+    assert_eq!(Node::Value { value: 5 }.fold(|v| v, |(a, b)| (a + b) as i64), 5);
+    assert_eq!(Node::B(1, 2).fold(|v| v, |(a, b)| (a + b) as i64), 3);
+}
+
+#[test]
+fn test_fold_names_like_generics() {
+    struct F0(u8);
+
+    #[derive(EnumFold)]
+    enum R {
+        A(F0),
+        B,
+    }
+
+    assert_eq!(R::A(F0(4)).fold(|F0(v)| v, || 0), 4);
+    assert_eq!(R::B.fold(|F0(v)| v, || 0), 0);
+}
@@ -0,0 +1,110 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumFunctor, Debug, PartialEq)]
+enum Annotated<T> {
+    Plain(T),
+    Tagged(String, T),
+    Pair { left: T, right: T, weight: u8 },
+    Missing,
+}
+
+#[test]
+fn test_map() {
+    assert_eq!(Annotated::Plain(2).map(|v| v * 10), Annotated::Plain(20));
+    assert_eq!(
+        Annotated::Tagged("len".to_string(), "four").map(str::len),
+        Annotated::Tagged("len".to_string(), 4)
+    );
+    assert_eq!(
+        Annotated::Pair { left: 1, right: 2, weight: 3 }.map(|v| v.to_string()),
+        Annotated::Pair { left: "1".to_string(), right: "2".to_string(), weight: 3 }
+    );
+    assert_eq!(Annotated::<i32>::Missing.map(|v| v + 1), Annotated::Missing);
+
+    let mut calls = 0;
+    Annotated::Pair { left: 1, right: 2, weight: 0 }.map(|_| calls += 1);
+    assert_eq!(calls, 2);
+}
+
+#[test]
+fn test_as_ref_and_as_mut() {
+    let tagged = Annotated::Tagged("tag".to_string(), vec![1, 2, 3]);
+    assert_eq!(tagged.as_ref().map(|v| v.len()), Annotated::Tagged("tag".to_string(), 3));
+
+    let mut pair = Annotated::Pair { left: 1, right: 2, weight: 7 };
+    if let Annotated::Pair { left, right, .. } = pair.as_mut() {
+        *left += 10;
+        *right += 20;
+    }
+    assert_eq!(pair, Annotated::Pair { left: 11, right: 22, weight: 7 });
+}
+
+#[test]
+fn test_functor_lifetimes() {
+    #[derive(EnumFunctor, Debug, PartialEq)]
+    enum Labeled<'a, U> {
+        Value(&'a str, U),
+        Empty(&'a str),
+    }
+
+    assert_eq!(Labeled::Value("x", 1).map(|v| v > 0), Labeled::Value("x", true));
+    assert_eq!(Labeled::<u8>::Empty("y").as_ref(), Labeled::Empty("y"));
+}
+
+#[test]
+fn test_functor_field_named_f() {
+    #[derive(EnumFunctor, Debug, PartialEq)]
+    enum Point<T> {
+        P { f: T, g: u8 },
+        None,
+    }
+
+    assert_eq!(Point::P { f: 1, g: 2 }.map(|v| v * 10), Point::P { f: 10, g: 2 });
+    assert_eq!(Point::P { f: 1, g: 2 }.as_ref(), Point::P { f: &1, g: 2 });
+}
+
+#[derive(EnumFunctor, Debug, PartialEq)]
+enum Tree<T> {
+    Leaf(T),
+    Maybe(Option<T>),
+    Many(Vec<Option<T>>),
+    Node(Box<Tree<T>>, Vec<Tree<T>>),
+    Labeled { label: String, child: Box<Option<T>> },
+}
+
+#[test]
+fn test_functor_nested_members() {
+    let tree = Tree::Node(
+        Box::new(Tree::Leaf(1)),
+        vec![Tree::Maybe(Some(2)), Tree::Maybe(None), Tree::Many(vec![Some(3), None])],
+    );
+    assert_eq!(
+        tree.map(|v| v * 10),
+        Tree::Node(
+            Box::new(Tree::Leaf(10)),
+            vec![Tree::Maybe(Some(20)), Tree::Maybe(None), Tree::Many(vec![Some(30), None])],
+        )
+    );
+
+    let labeled = Tree::Labeled { label: "x".to_string(), child: Box::new(Some(4)) };
+    assert_eq!(
+        labeled.as_ref(),
+        Tree::Labeled { label: "x".to_string(), child: Box::new(Some(&4)) }
+    );
+
+    let mut tree = Tree::Node(Box::new(Tree::Leaf(1)), vec![Tree::Many(vec![Some(2)])]);
+    if let Tree::Node(first, rest) = tree.as_mut() {
+        if let Tree::Leaf(v) = *first {
+            *v += 1;
+        }
+        if let Tree::Many(ref values) = rest[0] {
+            if let Some(ref v) = values[0] {
+                assert_eq!(**v, 2);
+            }
+        }
+    }
+    assert_eq!(tree, Tree::Node(Box::new(Tree::Leaf(2)), vec![Tree::Many(vec![Some(2)])]));
+}
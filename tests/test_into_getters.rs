@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 #[macro_use]
 extern crate enum_methods;
 
@@ -71,3 +73,21 @@ fn test_getter_structs() {
     );
     assert_eq!(third.into_some_struct(), 42);
 }
+
+#[test]
+fn test_positional_into_getters() {
+    #[derive(EnumIntoGetters, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Tup(i32, String, Vec<bool>),
+        #[enum_methods(fields(id, name))]
+        Named(i32, String),
+    }
+
+    let tup = || MyEnum::Tup(42, String::from("tuple"), vec![true]);
+    assert_eq!(tup().into_tup_0(), 42);
+    assert_eq!(tup().into_tup_1(), "tuple");
+    assert_eq!(tup().into_tup_2(), vec![true]);
+    assert_eq!(MyEnum::Named(7, String::from("named")).into_named_name(), "named");
+    assert_eq!(MyEnum::Named(7, String::from("named")).into_named_id(), 7);
+}
@@ -54,3 +54,51 @@ fn test_is_a_names() {
     assert!(second.is_bar_baz());
     assert!(third.is_baz());
 }
+
+#[test]
+fn test_is_a_groups() {
+    #[derive(EnumIsA, Debug)]
+    enum Value {
+        #[enum_methods(group = "numeric")]
+        Int(i64),
+        #[enum_methods(group = "numeric", group = "floating")]
+        Float(f64),
+        Bool(bool),
+        #[enum_methods(group = "numeric")]
+        #[enum_methods(group = "terminal")]
+        Complex { re: f64, im: f64 },
+        #[enum_methods(group = "terminal")]
+        Nil,
+    }
+
+    assert!(Value::Int(1).is_numeric());
+    assert!(Value::Float(1.5).is_numeric());
+    assert!(Value::Complex { re: 0.0, im: 1.0 }.is_numeric());
+    assert!(!Value::Bool(true).is_numeric());
+    assert!(!Value::Nil.is_numeric());
+    assert!(Value::Float(1.5).is_floating());
+    assert!(!Value::Int(1).is_floating());
+    assert!(Value::Nil.is_terminal());
+    assert!(Value::Int(1).is_int());
+
+    assert_eq!(Value::GROUP_NUMERIC, &["Int", "Float", "Complex"]);
+    assert_eq!(Value::GROUP_FLOATING, &["Float"]);
+    assert_eq!(Value::GROUP_TERMINAL, &["Complex", "Nil"]);
+}
+
+#[test]
+fn test_is_a_group_names_are_snake_cased() {
+    #[derive(EnumIsA, Debug)]
+    enum Number {
+        #[enum_methods(group = "FloatingPoint")]
+        Float(f64),
+        #[enum_methods(group = "FloatingPoint")]
+        Double(f64),
+        Int(i64),
+    }
+
+    assert!(Number::Float(1.0).is_floating_point());
+    assert!(Number::Double(1.0).is_floating_point());
+    assert!(!Number::Int(1).is_floating_point());
+    assert_eq!(Number::GROUP_FLOATING_POINT, &["Float", "Double"]);
+}
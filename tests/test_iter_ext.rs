@@ -0,0 +1,45 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumIterExt, Debug)]
+enum MyEnum {
+    Foo(i64),
+    Bus(bool),
+    Tup(i32, String),
+    SomeStruct { foo: i32 },
+    Unit,
+}
+
+fn values() -> Vec<MyEnum> {
+    vec![
+        MyEnum::Foo(1),
+        MyEnum::Bus(true),
+        MyEnum::Tup(2, "two".to_string()),
+        MyEnum::Foo(3),
+        MyEnum::Unit,
+        MyEnum::SomeStruct { foo: 4 },
+        MyEnum::Unit,
+    ]
+}
+
+#[test]
+fn test_iter_ext_borrowed() {
+    let values = values();
+    assert_eq!(values.iter().foos().collect::<Vec<_>>(), vec![&1, &3]);
+    assert_eq!(values.iter().buses().collect::<Vec<_>>(), vec![&true]);
+    assert_eq!(values.iter().tups().collect::<Vec<_>>(), vec![(&2, &"two".to_string())]);
+    assert_eq!(values.iter().foos().sum::<i64>(), 4);
+    assert_eq!(
+        values.iter().count_by_variant(),
+        [("Foo", 2), ("Bus", 1), ("Tup", 1), ("SomeStruct", 1), ("Unit", 2)]
+    );
+}
+
+#[test]
+fn test_iter_ext_owned() {
+    assert_eq!(values().into_iter().foos().collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(values().into_iter().tups().collect::<Vec<_>>(), vec![(2, "two".to_string())]);
+    assert_eq!(values().into_iter().skip(2).count_by_variant()[0], ("Foo", 1));
+}
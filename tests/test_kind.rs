@@ -0,0 +1,63 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+use std::collections::HashMap;
+
+#[test]
+fn test_kind() {
+    #[derive(EnumKind, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Bar(bool, i32),
+        SomeStruct { foo: i32 },
+        Unit,
+    }
+
+    let foo = MyEnum::Foo(42);
+    let bar = MyEnum::Bar(false, -3);
+    let some_struct = MyEnum::SomeStruct { foo: 42 };
+    let unit = MyEnum::Unit;
+
+    assert_eq!(foo.kind(), MyEnumKind::Foo);
+    assert_eq!(bar.kind(), MyEnumKind::Bar);
+    assert_eq!(some_struct.kind(), MyEnumKind::SomeStruct);
+    assert_eq!(unit.kind(), MyEnumKind::Unit);
+
+    assert!(foo.kind().is_foo());
+    assert!(!foo.kind().is_bar());
+    assert!(some_struct.kind().is_some_struct());
+    assert!(unit.kind().is_unit());
+
+    assert_eq!(MyEnumKind::COUNT, 4);
+    assert_eq!(
+        MyEnumKind::ALL,
+        [MyEnumKind::Foo, MyEnumKind::Bar, MyEnumKind::SomeStruct, MyEnumKind::Unit]
+    );
+    assert_eq!(MyEnumKind::SomeStruct.index(), 2);
+    assert_eq!(MyEnumKind::SomeStruct.name(), "SomeStruct");
+    assert!(MyEnumKind::Foo < MyEnumKind::Unit);
+
+    let mut counts = HashMap::new();
+    for e in &[foo, bar, some_struct, unit, MyEnum::Foo(7)] {
+        *counts.entry(e.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&MyEnumKind::Foo], 2);
+    assert_eq!(counts[&MyEnumKind::Unit], 1);
+}
+
+#[test]
+fn test_kind_attributes() {
+    #[derive(EnumKind, Debug)]
+    #[enum_methods(kind(name = "MessageType", derive(EnumKind)))]
+    enum Message {
+        Ping(u64),
+        Quit,
+    }
+
+    assert_eq!(Message::Ping(1).kind(), MessageType::Ping);
+    assert_eq!(Message::Quit.kind(), MessageType::Quit);
+    // the extra derive is applied to the generated kind enum
+    assert_eq!(MessageType::Quit.kind(), MessageTypeKind::Quit);
+}
@@ -0,0 +1,60 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_kind_map() {
+    #[derive(EnumKind, EnumKindMap, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Bar(bool, i32),
+        SomeStruct { foo: i32 },
+        Unit,
+    }
+
+    let mut counts = MyEnumKindMap::<usize>::default();
+    let values = vec![
+        MyEnum::Foo(42),
+        MyEnum::Bar(true, 1),
+        MyEnum::Unit,
+        MyEnum::Foo(7),
+    ];
+    for value in &values {
+        counts[value.kind()] += 1;
+    }
+
+    assert_eq!(counts[MyEnumKind::Foo], 2);
+    assert_eq!(*counts.get(MyEnumKind::Bar), 1);
+    assert_eq!(counts[MyEnumKind::SomeStruct], 0);
+    assert_eq!(counts.insert(MyEnumKind::Unit, 10), 1);
+    *counts.get_mut(MyEnumKind::SomeStruct) += 3;
+    assert_eq!(
+        counts.iter().collect::<Vec<_>>(),
+        vec![
+            (MyEnumKind::Foo, &2),
+            (MyEnumKind::Bar, &1),
+            (MyEnumKind::SomeStruct, &3),
+            (MyEnumKind::Unit, &10),
+        ]
+    );
+
+    for (_, count) in counts.iter_mut() {
+        *count = 0;
+    }
+    assert_eq!(counts, MyEnumKindMap::default());
+}
+
+#[test]
+fn test_kind_map_from_fn() {
+    #[derive(EnumKind, EnumKindMap)]
+    #[enum_methods(kind(name = "Op"))]
+    enum Instruction {
+        Push(i64),
+        Pop,
+    }
+
+    let names = OpMap::from_fn(|kind| kind.name().to_lowercase());
+    assert_eq!(names[Instruction::Push(1).kind()], "push");
+    assert_eq!(names[Op::Pop], "pop");
+}
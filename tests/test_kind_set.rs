@@ -0,0 +1,143 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_kind_set() {
+    #[derive(EnumKind, EnumKindSet, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Bar(bool, i32),
+        SomeStruct { foo: i32 },
+        Unit,
+    }
+
+    let mut set = MyEnumKindSet::new();
+    assert!(set.is_empty());
+    assert!(set.insert(MyEnumKind::Foo));
+    assert!(!set.insert(MyEnumKind::Foo));
+    set.insert(MyEnumKind::Unit);
+
+    assert!(set.contains(&MyEnum::Foo(42)));
+    assert!(set.contains(&MyEnum::Unit));
+    assert!(!set.contains(&MyEnum::Bar(true, 1)));
+    assert!(!set.contains_kind(MyEnumKind::SomeStruct));
+    assert_eq!(set.len(), 2);
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![MyEnumKind::Foo, MyEnumKind::Unit]);
+
+    assert!(set.remove(MyEnumKind::Unit));
+    assert!(!set.remove(MyEnumKind::Unit));
+    assert_eq!(set, MyEnumKindSet::from(MyEnumKind::Foo));
+    assert_eq!(format!("{:?}", set), "{Foo}");
+}
+
+#[test]
+fn test_kind_set_operators() {
+    #[derive(EnumKind, EnumKindSet, Debug)]
+    enum MyEnum {
+        Foo(i64),
+        Bar(bool, i32),
+        SomeStruct { foo: i32 },
+        Unit,
+    }
+
+    let first = [MyEnumKind::Foo, MyEnumKind::Bar].iter().cloned().collect::<MyEnumKindSet>();
+    let second = [MyEnumKind::Bar, MyEnumKind::Unit].iter().cloned().collect::<MyEnumKindSet>();
+
+    assert_eq!((first | second).len(), 3);
+    assert_eq!((first & second).iter().collect::<Vec<_>>(), vec![MyEnumKind::Bar]);
+    assert_eq!((first - second).iter().collect::<Vec<_>>(), vec![MyEnumKind::Foo]);
+    assert_eq!(MyEnumKindSet::ALL.len(), 4);
+    assert_eq!(MyEnumKindSet::ALL - MyEnumKindSet::ALL, MyEnumKindSet::EMPTY);
+
+    let mut set = MyEnumKindSet::EMPTY;
+    set |= first;
+    set &= second;
+    assert_eq!(set, MyEnumKindSet::from(MyEnumKind::Bar));
+}
+
+#[test]
+fn test_kind_set_many_variants() {
+    #[derive(EnumKind, EnumKindSet, Debug)]
+    enum Big {
+        V0,
+        V1,
+        V2,
+        V3,
+        V4,
+        V5,
+        V6,
+        V7,
+        V8,
+        V9,
+        V10,
+        V11,
+        V12,
+        V13,
+        V14,
+        V15,
+        V16,
+        V17,
+        V18,
+        V19,
+        V20,
+        V21,
+        V22,
+        V23,
+        V24,
+        V25,
+        V26,
+        V27,
+        V28,
+        V29,
+        V30,
+        V31,
+        V32,
+        V33,
+        V34,
+        V35,
+        V36,
+        V37,
+        V38,
+        V39,
+        V40,
+        V41,
+        V42,
+        V43,
+        V44,
+        V45,
+        V46,
+        V47,
+        V48,
+        V49,
+        V50,
+        V51,
+        V52,
+        V53,
+        V54,
+        V55,
+        V56,
+        V57,
+        V58,
+        V59,
+        V60,
+        V61,
+        V62,
+        V63,
+        V64,
+        V65,
+        V66,
+        V67,
+        V68,
+        V69
+    }
+
+    assert_eq!(BigKindSet::ALL.len(), 70);
+    let mut set = BigKindSet::EMPTY;
+    set.insert(BigKind::V3);
+    set.insert(BigKind::V69);
+    assert!(set.contains(&Big::V69));
+    assert!(!set.contains(&Big::V5));
+    assert_eq!(set.iter().collect::<Vec<_>>(), vec![BigKind::V3, BigKind::V69]);
+}
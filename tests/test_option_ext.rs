@@ -0,0 +1,112 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+use std::collections::HashMap;
+
+#[derive(EnumOptionExt, Debug)]
+enum Value {
+    Int(i64),
+    Text(String),
+    Pair(i32, i32),
+    Point { x: i32, y: i32 },
+    Nil,
+}
+
+#[test]
+fn test_option_ext_owned() {
+    let value = Some(Value::Text("hi".to_string()));
+    assert!(value.is_text());
+    assert!(!value.is_int());
+    assert_eq!(value.as_text().map(String::as_str), Some("hi"));
+    assert_eq!(value.as_int(), None);
+    assert_eq!(value.into_text(), Some("hi".to_string()));
+
+    let none: Option<Value> = None;
+    assert!(!none.is_nil());
+    assert_eq!(none.into_int(), None);
+
+    assert!(Some(Value::Point { x: 1, y: 2 }).is_point());
+    assert!(Some(Value::Pair(1, 2)).is_pair());
+}
+
+#[test]
+fn test_option_ext_ref() {
+    let mut map = HashMap::new();
+    map.insert("a", Value::Int(1));
+    map.insert("b", Value::Nil);
+
+    assert!(map.get("a").is_int());
+    assert!(map.get("b").is_nil());
+    assert!(!map.get("c").is_nil());
+    assert_eq!(map.get("a").into_int(), Some(&1));
+    assert_eq!(map.get("a").as_int(), Some(&1));
+    assert_eq!(map.get("b").into_int(), None);
+
+    if let Some(v) = map.get_mut("a").into_int() {
+        *v += 1;
+    }
+    assert!(map.get_mut("a").is_int());
+    assert_eq!(map.get_mut("a").as_int(), Some(&2));
+    assert_eq!(map.get_mut("c").into_int(), None);
+}
+
+#[test]
+fn test_result_ext() {
+    let ok: Result<Value, ()> = Ok(Value::Int(3));
+    assert!(ok.is_int());
+    assert_eq!(ok.as_int(), Some(&3));
+    assert_eq!(ok.into_int(), Some(3));
+
+    let err: Result<Value, &str> = Err("oops");
+    assert!(!err.is_int());
+    assert_eq!(err.into_text(), None);
+
+    let mut value = Value::Text("a".to_string());
+    let borrowed: Result<&Value, ()> = Ok(&value);
+    assert_eq!(borrowed.into_text().map(String::as_str), Some("a"));
+    let borrowed_mut: Result<&mut Value, ()> = Ok(&mut value);
+    borrowed_mut.into_text().unwrap().push('b');
+    assert_eq!(Ok::<_, ()>(&value).as_text().map(String::as_str), Some("ab"));
+}
+
+#[test]
+fn test_option_ext_enum_named_e() {
+    #[derive(EnumOptionExt, Debug)]
+    enum E {
+        Num(u8),
+        Nil,
+    }
+
+    let ok: Result<E, String> = Ok(E::Num(1));
+    assert_eq!(ok.as_num(), Some(&1));
+    let borrowed: Result<&E, ()> = Ok(&E::Nil);
+    assert!(borrowed.is_nil());
+    assert!(Some(E::Nil).is_nil());
+}
+
+#[test]
+fn test_option_ext_prefix() {
+    #[derive(EnumOptionExt, Debug)]
+    #[enum_methods(option_ext(prefix = "status"))]
+    enum Status {
+        Ok,
+        Err(String),
+        Some(u8),
+        None,
+    }
+
+    let err: Result<Status, ()> = Ok(Status::Err("bad".to_string()));
+    assert!(err.is_ok());
+    assert!(!err.is_status_ok());
+    assert!(err.is_status_err());
+    assert_eq!(err.as_status_err().map(String::as_str), Some("bad"));
+
+    let some = Some(Status::None);
+    assert!(some.is_some());
+    assert!(some.is_status_none());
+    assert!(!some.is_status_some());
+    assert_eq!(Some(Status::Some(1)).into_status_some(), Some(1));
+    assert!(!None::<Status>.is_status_none());
+}
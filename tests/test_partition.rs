@@ -0,0 +1,54 @@
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_partition() {
+    #[derive(EnumPartition, Debug)]
+    #[enum_methods(partition(derive(Debug, PartialEq)))]
+    enum Event {
+        Foo(i64),
+        Bar(bool),
+        Tup(i32, String, Vec<bool>),
+        SomeStruct { foo: i32 },
+        Unit,
+    }
+
+    let events = vec![
+        Event::Foo(1),
+        Event::Unit,
+        Event::Tup(2, "two".to_string(), vec![true]),
+        Event::Foo(3),
+        Event::SomeStruct { foo: 4 },
+        Event::Unit,
+    ];
+
+    let mut partition = events.into_iter().collect::<EventPartition>();
+    assert_eq!(partition.foo, vec![1, 3]);
+    assert!(partition.bar.is_empty());
+    assert_eq!(partition.tup, vec![(2, "two".to_string(), vec![true])]);
+    assert_eq!(partition.some_struct, vec![4]);
+    assert_eq!(partition.unit_count, 2);
+
+    partition.extend(vec![Event::Bar(false), Event::Unit]);
+    assert_eq!(partition.bar, vec![false]);
+    assert_eq!(partition.unit_count, 3);
+
+    assert_eq!(Vec::<Event>::new().into_iter().collect::<EventPartition>(), EventPartition::default());
+}
+
+#[test]
+fn test_partition_keyword_variants() {
+    #[derive(EnumPartition)]
+    enum Tok {
+        Type(u8),
+        Ident(String),
+        Fn,
+    }
+
+    let partition = vec![Tok::Type(1), Tok::Ident("x".to_string()), Tok::Fn, Tok::Type(2)]
+        .into_iter()
+        .collect::<TokPartition>();
+    assert_eq!(partition.type_, vec![1, 2]);
+    assert_eq!(partition.ident, vec!["x".to_string()]);
+    assert_eq!(partition.fn_count, 1);
+}
@@ -0,0 +1,61 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[test]
+fn test_payload_eq() {
+    #[derive(EnumPayloadEq, Debug)]
+    enum Token {
+        Ident(String),
+        Num(f64),
+        Pair(i32, i32),
+        Eof,
+    }
+
+    let tok = Token::Ident("fn".to_string());
+    assert!(tok.ident_eq("fn"));
+    assert!(tok.ident_eq(&"fn".to_string()));
+    assert!(!tok.ident_eq("let"));
+    assert!(!tok.num_eq(&1.0));
+
+    let num = Token::Num(1.5);
+    assert!(num.num_eq(&1.5));
+    assert!(!num.ident_eq("1.5"));
+    assert!(!Token::Eof.num_eq(&0.0));
+}
+
+#[test]
+fn test_payload_partial_eq() {
+    #[derive(EnumPayloadEq, Debug)]
+    #[enum_methods(payload_eq)]
+    enum Value {
+        Int(i64),
+        Name(String),
+        Alias(String),
+        Nil,
+    }
+
+    assert!(Value::Int(42) == 42);
+    assert!(Value::Nil != 42);
+    assert_eq!(Value::Int(42), 42);
+    assert_ne!(Value::Int(41), 42);
+    // `String` is held by two variants, so only the methods are available for it
+    assert!(Value::Name("x".to_string()).name_eq("x"));
+    assert!(!Value::Alias("x".to_string()).name_eq("x"));
+}
+
+#[test]
+fn test_payload_eq_type_named_u() {
+    #[derive(Debug, PartialEq)]
+    struct U(u8);
+
+    #[derive(EnumPayloadEq, Debug)]
+    enum Holder {
+        Value(U),
+        Empty,
+    }
+
+    assert!(Holder::Value(U(1)).value_eq(&U(1)));
+    assert!(!Holder::Empty.value_eq(&U(1)));
+}
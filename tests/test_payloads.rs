@@ -0,0 +1,70 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+use std::convert::TryFrom;
+
+#[derive(EnumPayloads, Debug, PartialEq)]
+enum MyEnum {
+    Foo(i64),
+    #[enum_methods(payload(derive(Debug, PartialEq)))]
+    Tup(i32, String, Vec<bool>),
+    #[enum_methods(payload)]
+    SomeStruct { foo: i32, bar: String },
+    Unit,
+}
+
+fn takes_tup(tup: MyEnumTup) -> usize {
+    tup.1.len() + tup.2.len()
+}
+
+#[test]
+fn test_payload_structs() {
+    let tup = MyEnum::Tup(42, String::from("tuple"), vec![true]);
+    let view = tup.as_tup_payload();
+    assert_eq!(*view.0, 42);
+    assert_eq!(view.1, "tuple");
+
+    let payload = tup.into_tup_payload();
+    assert_eq!(payload, MyEnumTup(42, String::from("tuple"), vec![true]));
+    assert_eq!(takes_tup(payload), 6);
+
+    let some_struct = MyEnum::SomeStruct { foo: 1, bar: String::from("bar") };
+    assert_eq!(*some_struct.as_some_struct_payload().foo, 1);
+    let payload = some_struct.into_some_struct_payload();
+    assert_eq!(payload.bar, "bar");
+}
+
+#[test]
+fn test_payload_conversions() {
+    let value = MyEnum::from(MyEnumSomeStruct { foo: 2, bar: String::from("two") });
+    assert_eq!(value, MyEnum::SomeStruct { foo: 2, bar: String::from("two") });
+    assert_eq!(MyEnumSomeStruct::try_from(value).unwrap().foo, 2);
+
+    let value = MyEnum::from(MyEnumTup(1, String::new(), vec![]));
+    assert_eq!(value, MyEnum::Tup(1, String::new(), vec![]));
+    assert_eq!(MyEnumSomeStruct::try_from(value).err(), Some(MyEnum::Tup(1, String::new(), vec![])));
+    assert_eq!(MyEnumTup::try_from(MyEnum::Unit), Err(MyEnum::Unit));
+}
+
+#[test]
+#[should_panic(expected = "called into_tup_payload() on Foo(1)")]
+fn test_payload_wrong_variant() {
+    MyEnum::Foo(1).into_tup_payload();
+}
+
+#[test]
+fn test_enum_payloads() {
+    #[derive(EnumPayloads, Debug)]
+    #[enum_methods(payload)]
+    enum Shape {
+        Circle(f64),
+        Rect { width: f64, height: f64 },
+        Empty,
+    }
+
+    let rect = ShapeRect::try_from(Shape::Rect { width: 2.0, height: 3.0 }).unwrap();
+    assert_eq!(rect.width * rect.height, 6.0);
+    assert_eq!(Shape::Circle(1.5).into_circle_payload().0, 1.5);
+}
@@ -0,0 +1,81 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumSetters, Debug, PartialEq)]
+enum MyEnum {
+    Foo(i64),
+    Bar(bool),
+    Tup(i32, String, Vec<bool>),
+    SomeStruct { foo: i32 },
+    Unit,
+}
+
+#[test]
+fn test_set() {
+    let mut foo = MyEnum::Foo(42);
+    assert_eq!(foo.set_foo(7), Ok(()));
+    assert_eq!(foo, MyEnum::Foo(7));
+    assert_eq!(foo.set_bar(true), Err(true));
+    assert_eq!(foo, MyEnum::Foo(7));
+
+    let mut tup = MyEnum::Tup(1, "one".to_string(), vec![]);
+    assert_eq!(tup.set_tup((2, "two".to_string(), vec![true])), Ok(()));
+    assert_eq!(tup, MyEnum::Tup(2, "two".to_string(), vec![true]));
+    assert_eq!(tup.set_foo(3), Err(3));
+}
+
+#[test]
+fn test_replace() {
+    let mut value = MyEnum::Foo(42);
+    assert_eq!(value.replace_foo(7), Some(42));
+    assert_eq!(value, MyEnum::Foo(7));
+    assert_eq!(value.replace_bar(true), None);
+    assert_eq!(value, MyEnum::Bar(true));
+
+    let mut tup = MyEnum::Unit;
+    assert_eq!(tup.replace_tup((1, "one".to_string(), vec![])), None);
+    assert_eq!(
+        tup.replace_tup((2, "two".to_string(), vec![false])),
+        Some((1, "one".to_string(), vec![]))
+    );
+}
+
+#[test]
+fn test_set_variant() {
+    let mut value = MyEnum::SomeStruct { foo: 1 };
+    value.set_variant_foo(7);
+    assert_eq!(value, MyEnum::Foo(7));
+    value.set_variant_tup((1, "one".to_string(), vec![true]));
+    assert_eq!(value, MyEnum::Tup(1, "one".to_string(), vec![true]));
+}
+
+#[test]
+fn test_take() {
+    #[derive(EnumSetters, Debug, PartialEq)]
+    enum State {
+        Running(String),
+        Paused(String, u32),
+        #[enum_methods(default)]
+        Idle,
+    }
+
+    struct Machine {
+        state: State,
+    }
+
+    let mut machine = Machine { state: State::default() };
+    assert_eq!(machine.state, State::Idle);
+    assert_eq!(machine.state.take_running(), None);
+
+    machine.state = State::Running("job".to_string());
+    assert_eq!(machine.state.take_paused(), None);
+    assert_eq!(machine.state, State::Running("job".to_string()));
+    assert_eq!(machine.state.take_running(), Some("job".to_string()));
+    assert_eq!(machine.state, State::Idle);
+
+    machine.state = State::Paused("job".to_string(), 3);
+    assert_eq!(machine.state.take_paused(), Some(("job".to_string(), 3)));
+    assert_eq!(machine.state, State::Idle);
+}
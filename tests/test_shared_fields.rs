@@ -0,0 +1,66 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Span {
+    start: usize,
+    end: usize,
+}
+
+#[test]
+fn test_shared_fields() {
+    #[derive(EnumSharedFields, Debug)]
+    enum Expr {
+        Number { value: i64, span: Span },
+        Name { name: String, span: Span, id: u32 },
+        Call { name: String, args: Vec<Expr>, span: Span, id: u32 },
+        Tagged { value: bool, span: Span },
+    }
+
+    let mut number = Expr::Number { value: 1, span: Span { start: 0, end: 1 } };
+    let call = Expr::Call {
+        name: "f".to_string(),
+        args: vec![],
+        span: Span { start: 2, end: 5 },
+        id: 7,
+    };
+
+    // shared by every variant
+    assert_eq!(*number.span(), Span { start: 0, end: 1 });
+    assert_eq!(call.span().end, 5);
+    number.span_mut().end = 3;
+    assert_eq!(number.span().end, 3);
+
+    // shared by some variants
+    assert_eq!(call.id(), Some(&7));
+    assert_eq!(number.id(), None);
+    assert_eq!(call.name().map(String::as_str), Some("f"));
+    assert!(number.name_mut().is_none());
+}
+
+#[test]
+fn test_selected_shared_fields() {
+    #[derive(EnumSharedFields, Debug)]
+    #[enum_methods(shared(span, label))]
+    enum Node {
+        Leaf { span: Span, label: String, id: u32 },
+        Branch { span: Span, id: u32 },
+        Empty,
+    }
+
+    impl Node {
+        // not generated, since only `span` and `label` were selected
+        fn id(&self) -> u32 {
+            0
+        }
+    }
+
+    let mut leaf = Node::Leaf { span: Span { start: 1, end: 2 }, label: "leaf".to_string(), id: 1 };
+    assert_eq!(leaf.span(), Some(&Span { start: 1, end: 2 }));
+    assert_eq!(Node::Empty.span(), None);
+    leaf.label_mut().unwrap().push('!');
+    assert_eq!(leaf.label().unwrap(), "leaf!");
+    assert_eq!(leaf.id(), 0);
+}
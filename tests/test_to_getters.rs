@@ -92,3 +92,47 @@ fn test_getter_structs() {
     );
     assert_eq!(third.into_some_struct(), 42);
 }
+
+#[test]
+#[allow(clippy::duplicated_attributes)]
+fn test_group_to_getters() {
+    #[derive(EnumToGetters, Debug)]
+    #[enum_methods(getter(name = "as_number", ty = "f64", via = "as", variants(Int, Float)))]
+    #[enum_methods(getter(name = "as_float", ty = "f64", variants(Float, Bool)))]
+    #[enum_methods(getter(name = "as_small", ty = "u8", via = "TryInto", variants(Int)))]
+    enum Value {
+        Int(i64),
+        Float(f64),
+        Bool(bool),
+        Pair(i64, i64),
+        Nil,
+    }
+
+    assert_eq!(Value::Int(3).as_number(), Some(3.0));
+    assert_eq!(Value::Float(1.5).as_number(), Some(1.5));
+    assert_eq!(Value::Bool(true).as_number(), None);
+    assert_eq!(Value::Nil.as_number(), None);
+
+    assert_eq!(Value::Bool(true).as_float(), Some(1.0));
+    assert_eq!(Value::Int(3).as_float(), None);
+
+    assert_eq!(Value::Int(3).as_small(), Some(3));
+    assert_eq!(Value::Int(300).as_small(), None);
+    assert_eq!(Value::Pair(1, 2).as_small(), None);
+
+    #[derive(EnumToGetters, Debug)]
+    #[enum_methods(getter(name = "to_id", ty = "u64"))]
+    enum Id {
+        #[enum_methods(getter = "to_id")]
+        Small(u8),
+        #[enum_methods(getter = "to_id")]
+        Medium(u32),
+        Large(u128),
+        Pair(u8, u8),
+    }
+
+    assert_eq!(Id::Small(3).to_id(), Some(3));
+    assert_eq!(Id::Medium(70000).to_id(), Some(70000));
+    assert_eq!(Id::Large(1).to_id(), None);
+    assert_eq!(Id::Pair(1, 2).to_id(), None);
+}
@@ -0,0 +1,72 @@
+#![allow(dead_code)]
+
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumViews, Debug, Clone, PartialEq)]
+enum MyEnum {
+    Foo(i64),
+    Tup(i32, String),
+    SomeStruct { foo: i32, bar: Vec<bool> },
+    Unit,
+}
+
+fn describe(view: MyEnumRef) -> String {
+    match view {
+        MyEnumRef::Foo(v) => format!("foo {}", v),
+        MyEnumRef::Tup(a, b) => format!("tup {} {}", a, b),
+        MyEnumRef::SomeStruct { foo: f, bar: b } => format!("struct {} {}", f, b.len()),
+        MyEnumRef::Unit => "unit".to_string(),
+    }
+}
+
+#[test]
+fn test_views() {
+    let values = vec![
+        MyEnum::Foo(42),
+        MyEnum::Tup(1, "one".to_string()),
+        MyEnum::SomeStruct { foo: 2, bar: vec![true] },
+        MyEnum::Unit,
+    ];
+    let described = values.iter().map(|v| describe(v.as_view())).collect::<Vec<_>>();
+    assert_eq!(described, vec!["foo 42", "tup 1 one", "struct 2 1", "unit"]);
+
+    for value in &values {
+        assert_eq!(value.as_view().to_owned(), *value);
+    }
+}
+
+#[test]
+fn test_view_mut() {
+    let mut value = MyEnum::SomeStruct { foo: 2, bar: vec![] };
+    if let MyEnumMut::SomeStruct { foo: f, bar: b } = value.as_view_mut() {
+        *f += 1;
+        b.push(true);
+    }
+    assert_eq!(value, MyEnum::SomeStruct { foo: 3, bar: vec![true] });
+
+    let mut tup = MyEnum::Tup(1, "one".to_string());
+    match tup.as_view_mut() {
+        MyEnumMut::Tup(_, s) => s.push('!'),
+        _ => unreachable!(),
+    }
+    assert_eq!(tup, MyEnum::Tup(1, "one!".to_string()));
+}
+
+#[test]
+fn test_views_without_clone() {
+    struct NotClone(u8);
+
+    #[derive(EnumViews)]
+    enum Holder {
+        Value(NotClone),
+        Nothing,
+    }
+
+    // to_owned isn't available, but the views still are
+    let holder = Holder::Value(NotClone(3));
+    match holder.as_view() {
+        HolderRef::Value(v) => assert_eq!(v.0, 3),
+        HolderRef::Nothing => unreachable!(),
+    }
+}
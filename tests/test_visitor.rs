@@ -0,0 +1,117 @@
+#[macro_use]
+extern crate enum_methods;
+
+#[derive(EnumVisitor, Debug, PartialEq)]
+enum Expr {
+    Number(i64),
+    Add(Box<Expr>, Box<Expr>),
+    Neg { operand: Box<Expr> },
+    Zero,
+}
+
+struct Evaluator;
+
+impl ExprVisitor for Evaluator {
+    type Output = i64;
+
+    fn visit_number(&mut self, a: &i64) -> i64 {
+        *a
+    }
+
+    fn visit_add(&mut self, a: &Box<Expr>, b: &Box<Expr>) -> i64 {
+        a.accept(self) + b.accept(self)
+    }
+
+    fn visit_neg(&mut self, operand: &Box<Expr>) -> i64 {
+        -operand.accept(self)
+    }
+
+    fn visit_zero(&mut self) -> i64 {
+        0
+    }
+}
+
+struct Doubler;
+
+impl ExprVisitorMut for Doubler {
+    type Output = ();
+
+    fn visit_number(&mut self, a: &mut i64) {
+        *a *= 2;
+    }
+
+    fn visit_add(&mut self, a: &mut Box<Expr>, b: &mut Box<Expr>) {
+        a.accept_mut(self);
+        b.accept_mut(self);
+    }
+
+    fn visit_neg(&mut self, operand: &mut Box<Expr>) {
+        operand.accept_mut(self);
+    }
+
+    fn visit_zero(&mut self) {}
+}
+
+#[test]
+fn test_visitor() {
+    let mut expr = Expr::Add(
+        Box::new(Expr::Number(40)),
+        Box::new(Expr::Neg { operand: Box::new(Expr::Add(Box::new(Expr::Number(-2)), Box::new(Expr::Zero))) }),
+    );
+    assert_eq!(expr.accept(&mut Evaluator), 42);
+
+    expr.accept_mut(&mut Doubler);
+    assert_eq!(expr.accept(&mut Evaluator), 84);
+}
+
+#[test]
+fn test_visitor_field_named_visitor() {
+    #[derive(EnumVisitor)]
+    enum Step {
+        Walk { visitor: u8 },
+        Stop,
+    }
+
+    struct Counter;
+
+    impl StepVisitor for Counter {
+        type Output = u8;
+
+        fn visit_walk(&mut self, visitor: &u8) -> u8 {
+            *visitor
+        }
+
+        fn visit_stop(&mut self) -> u8 {
+            0
+        }
+    }
+
+    assert_eq!(Step::Walk { visitor: 3 }.accept(&mut Counter), 3);
+    assert_eq!(Step::Stop.accept(&mut Counter), 0);
+}
+
+#[test]
+fn test_visitor_enum_named_v() {
+    #[derive(EnumVisitor)]
+    enum V {
+        A(u8),
+        B,
+    }
+
+    struct Sum;
+
+    impl VVisitor for Sum {
+        type Output = u8;
+
+        fn visit_a(&mut self, a: &u8) -> u8 {
+            *a
+        }
+
+        fn visit_b(&mut self) -> u8 {
+            0
+        }
+    }
+
+    assert_eq!(V::A(2).accept(&mut Sum), 2);
+    assert_eq!(V::B.accept(&mut Sum), 0);
+}